@@ -1,3 +1,7 @@
+// Nothing consumes the parsed definitions outside of the validation
+// tests until the register block macro lands, see the `expand` module.
+#![allow(dead_code)]
+
 use syn::{
     braced,
     parse::{Parse, ParseStream, Result},
@@ -17,32 +21,91 @@ pub struct RegisterBlock {
     pub attrs: Vec<syn::Attribute>,
     pub vis: syn::Visibility,
     pub ident: syn::Ident,
-    pub registers: Punctuated<RegisterLayout, Token![,]>,
+    pub entries: Punctuated<BlockEntry, Token![,]>,
+}
+
+impl RegisterBlock {
+    /// Iterates over the registers in the block, skipping reserved regions.
+    pub fn registers(&self) -> impl Iterator<Item = &RegisterLayout> {
+        self.entries.iter().filter_map(|entry| match entry {
+            BlockEntry::Register(layout) => Some(layout),
+            BlockEntry::Reserved(_) => None,
+        })
+    }
+}
+
+/// An entry in a [`RegisterBlock`].
+#[allow(clippy::large_enum_variant)]
+pub enum BlockEntry {
+    Register(RegisterLayout),
+    Reserved(Reserved),
+}
+
+/// A reserved region of a given size in bytes inside a [`RegisterBlock`].
+///
+/// The region directly follows the preceding entry and is padded over
+/// so that subsequent registers stay at their offsets.
+pub struct Reserved {
+    pub attrs: Vec<syn::Attribute>,
+    pub size: syn::LitInt,
+}
+
+impl Reserved {
+    /// Gets the size of the reserved region in bytes.
+    pub fn size(&self) -> Result<usize> {
+        self.size.base10_parse()
+    }
 }
 
 /// The memory layout of a register as part of a [`RegisterBlock`] struct.
 ///
 /// This struct encodes information on the [`RegisterDef`] and the relative
 /// offset from a base address where it is mapped in memory.
+///
+/// The offset may be any constant expression, e.g. `BASE + 0x10`. It is
+/// not forwarded into any generated layout yet, as there is no expansion.
 pub struct RegisterLayout {
     pub attrs: Vec<syn::Attribute>,
-    pub addr: syn::LitInt,
+    pub addr: syn::Expr,
     pub reg: RegisterDef,
 }
 
 impl RegisterLayout {
-    /// Gets the address offset of the register in memory.
-    pub fn address(&self) -> Result<usize> {
-        self.addr.base10_parse()
+    /// Gets the address offset of the register in memory if it is given
+    /// as an integer literal.
+    ///
+    /// Returns `None` for other constant expressions, which can only be
+    /// evaluated by the compiler.
+    pub fn address(&self) -> Result<Option<usize>> {
+        match &self.addr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => int.base10_parse().map(Some),
+            syn::Expr::Lit(lit) => Err(syn::Error::new_spanned(
+                lit,
+                "expected an integer literal or constant expression",
+            )),
+            _ => Ok(None),
+        }
     }
 }
 
 /// A register definition with its name, type and several [`BitField`]s.
+///
+/// When `len` is given, the definition describes an array of identical
+/// registers laid out back to back. `reset` is the documented power-on
+/// value of the register, if any.
+///
+/// Neither is expanded yet: without a macro entry point, no `RESET`
+/// constant, `reset()` method or array accessor is generated from them.
 pub struct RegisterDef {
     pub attrs: Vec<syn::Attribute>,
     pub vis: syn::Visibility,
     pub ident: syn::Ident,
+    pub len: Option<syn::LitInt>,
     pub ty: syn::Type,
+    pub reset: Option<syn::LitInt>,
     pub fields: Punctuated<BitField, Token![,]>,
 }
 
@@ -106,7 +169,7 @@ impl RegisterRange {
                 let end = Self::extract_int_from_range(&range.to)?;
 
                 if let Some(end) = end {
-                    if start < end {
+                    if end < start {
                         return Err(syn::Error::new_spanned(
                             &range,
                             "end of range must not be smaller than start of range",
@@ -114,13 +177,22 @@ impl RegisterRange {
                     }
                 }
 
-                Ok(end
+                let width = end
                     .map(|i| {
                         // Fix the range value by adding `1` when the end is inclusive.
                         let inclusive_end = matches!(range.limits, syn::RangeLimits::Closed(_));
                         i + inclusive_end as usize
                     })
-                    .map(|i| i - start))
+                    .map(|i| i - start);
+
+                if width == Some(0) {
+                    return Err(syn::Error::new_spanned(
+                        range,
+                        "range is empty, fields must be at least one bit wide",
+                    ));
+                }
+
+                Ok(width)
             }
         }
     }
@@ -131,6 +203,8 @@ pub enum Permission {
     Read,
     Write,
     ReadWrite,
+    WriteOneToClear,
+    ReadToClear,
 }
 
 impl<P: Parse> Parse for Input<P> {
@@ -167,17 +241,46 @@ impl Parse for RegisterBlock {
 
         let content;
         braced!(content in input);
-        let registers = content.parse_terminated(RegisterLayout::parse)?;
+        let entries = content.parse_terminated(BlockEntry::parse)?;
 
         Ok(Self {
             attrs,
             vis,
             ident,
-            registers,
+            entries,
         })
     }
 }
 
+impl Parse for BlockEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+
+        if input.peek(Token![_]) {
+            // _ => reserved(SIZE)
+            input.parse::<Token![_]>()?;
+            input.parse::<Token![=]>()?;
+            input.parse::<Token![>]>()?;
+
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "reserved" {
+                return Err(syn::Error::new_spanned(ident, "expected `reserved`"));
+            }
+
+            let content;
+            syn::parenthesized!(content in input);
+            let size = content.parse()?;
+
+            Ok(BlockEntry::Reserved(Reserved { attrs, size }))
+        } else {
+            let mut layout: RegisterLayout = input.parse()?;
+            layout.attrs.splice(0..0, attrs);
+
+            Ok(BlockEntry::Register(layout))
+        }
+    }
+}
+
 impl Parse for RegisterLayout {
     fn parse(input: ParseStream) -> Result<Self> {
         let attrs = input.call(syn::Attribute::parse_outer)?;
@@ -197,9 +300,33 @@ impl Parse for RegisterDef {
         let vis = input.parse()?;
         let ident = input.parse()?;
 
+        let len = if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+
         input.parse::<Token![as]>()?;
         let ty = input.parse()?;
 
+        let reset = if input.peek(Token![=]) {
+            // = reset(VALUE)
+            input.parse::<Token![=]>()?;
+
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "reset" {
+                return Err(syn::Error::new_spanned(ident, "expected `reset`"));
+            }
+
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+
         let content;
         braced!(content in input);
         let fields = content.parse_terminated(BitField::parse)?;
@@ -208,7 +335,9 @@ impl Parse for RegisterDef {
             attrs,
             vis,
             ident,
+            len,
             ty,
+            reset,
             fields,
         })
     }
@@ -257,17 +386,43 @@ impl Parse for FieldOptions {
 
 impl Parse for RegisterRange {
     fn parse(input: ParseStream) -> Result<Self> {
-        match input.parse::<syn::Expr>()? {
-            syn::Expr::Lit(lit) => match lit.lit {
-                syn::Lit::Int(int) => Ok(RegisterRange::Lit(int)),
-                lit => Err(syn::Error::new_spanned(lit, "expected an integer literal")),
-            },
-            syn::Expr::Range(range) => Ok(RegisterRange::Range(range)),
-            expr => Err(syn::Error::new_spanned(
-                expr,
-                "expected a numeric literal or a range expression denoting the field width",
-            )),
+        // The range is parsed by hand because a `syn::Expr` would greedily
+        // consume trailing `= enum ...` field options as an assignment.
+        let from = if input.peek(syn::Lit) {
+            Some(input.parse::<syn::Lit>()?)
+        } else {
+            None
+        };
+
+        if !input.peek(Token![..]) {
+            return match from {
+                Some(syn::Lit::Int(int)) => Ok(RegisterRange::Lit(int)),
+                Some(lit) => Err(syn::Error::new_spanned(lit, "expected an integer literal")),
+                None => Err(input.error(
+                    "expected a numeric literal or a range expression denoting the field width",
+                )),
+            };
         }
+
+        let limits = input.parse()?;
+        let to = if input.peek(syn::Lit) {
+            Some(input.parse::<syn::Lit>()?)
+        } else {
+            None
+        };
+
+        let lit_expr = |lit| {
+            Box::new(syn::Expr::Lit(syn::ExprLit {
+                attrs: Vec::new(),
+                lit,
+            }))
+        };
+        Ok(RegisterRange::Range(syn::ExprRange {
+            attrs: Vec::new(),
+            from: from.map(lit_expr),
+            limits,
+            to: to.map(lit_expr),
+        }))
     }
 }
 
@@ -279,9 +434,11 @@ impl Parse for Permission {
             "r" => Ok(Permission::Read),
             "w" => Ok(Permission::Write),
             "rw" => Ok(Permission::ReadWrite),
+            "w1c" => Ok(Permission::WriteOneToClear),
+            "rc" => Ok(Permission::ReadToClear),
             _ => Err(syn::Error::new_spanned(
                 ident,
-                "unrecognized permissions - r/w/rw are supported values",
+                "unrecognized permissions - r/w/rw/w1c/rc are supported values",
             )),
         }
     }
@@ -1 +1,901 @@
-
+//! Validation and expansion of parsed register definitions.
+//!
+//! This crate does not export a macro entry point yet, so none of the
+//! passes below run on user code and no diagnostics reach users. They
+//! are only exercised by the unit tests over parsed input at the bottom
+//! of this file. Until the register block macro lands, requests relying
+//! on its diagnostics remain blocked, and so do UI tests for them.
+//! Until then, the passes are exempt from dead code warnings outside of
+//! tests.
+
+#![cfg_attr(not(test), allow(dead_code))]
+
+use std::ops::Range;
+
+use syn::{Error, Result};
+
+use crate::ast::{
+    BitField, BlockEntry, FieldOptions, Permission, RegisterBlock, RegisterDef, Reserved,
+};
+
+/// Validates all register definitions in a [`RegisterBlock`].
+///
+/// Every invalid definition is reported instead of bailing out
+/// after the first error.
+pub fn validate_block(block: &RegisterBlock) -> Result<()> {
+    let mut errors = None;
+    for entry in &block.entries {
+        match entry {
+            BlockEntry::Register(layout) => collect(&mut errors, validate_register(&layout.reg)),
+            BlockEntry::Reserved(reserved) => collect(&mut errors, validate_reserved(reserved)),
+        }
+    }
+    collect(&mut errors, validate_addresses(block));
+    collect(&mut errors, validate_reserved_offsets(block));
+
+    finish(errors)
+}
+
+/// Checks that the memory ranges of the registers in a block, spanning
+/// their full width and all array elements, don't overlap.
+fn validate_addresses(block: &RegisterBlock) -> Result<()> {
+    let mut errors = None;
+
+    let mut seen: Vec<(Range<usize>, &RegisterDef)> = Vec::new();
+    for layout in block.registers() {
+        // Overlaps with constant expressions are left undetected.
+        let addr = match layout.address() {
+            Ok(Some(addr)) => addr,
+            Ok(None) => continue,
+            Err(e) => {
+                collect(&mut errors, Err(e));
+                continue;
+            }
+        };
+        // Registers of unsupported types are reported elsewhere, so
+        // only check their start address.
+        let range = addr..addr + register_size(&layout.reg).unwrap_or(1);
+
+        let overlap = seen
+            .iter()
+            .find(|(other, _)| range.start < other.end && other.start < range.end);
+        match overlap {
+            Some((other, reg)) if other.start == range.start => collect(
+                &mut errors,
+                Err(Error::new_spanned(
+                    &layout.addr,
+                    format!(
+                        "register `{}` is mapped to the same address as `{}`",
+                        layout.reg.ident, reg.ident
+                    ),
+                )),
+            ),
+            Some((other, reg)) => collect(
+                &mut errors,
+                Err(Error::new_spanned(
+                    &layout.addr,
+                    format!(
+                        "register `{}` at {:#x}..{:#x} overlaps `{}` at {:#x}..{:#x}",
+                        layout.reg.ident, range.start, range.end, reg.ident, other.start, other.end
+                    ),
+                )),
+            ),
+            None => seen.push((range, &layout.reg)),
+        }
+    }
+
+    finish(errors)
+}
+
+/// Checks that every reserved region ends exactly where the next
+/// register is mapped.
+///
+/// Regions are laid out directly after the preceding entry, starting
+/// at offset `0` for the first entry. Offsets which depend on constant
+/// expressions or invalid definitions are not checked.
+fn validate_reserved_offsets(block: &RegisterBlock) -> Result<()> {
+    let mut errors = None;
+
+    // The offset right after the previous entry, if it is known.
+    let mut end = Some(0);
+    let mut reserved = None;
+    for entry in &block.entries {
+        match entry {
+            BlockEntry::Reserved(region) => {
+                end = end.zip(region.size().ok()).map(|(end, size)| end + size);
+                reserved = Some(region);
+            }
+            BlockEntry::Register(layout) => {
+                let addr = layout.address().ok().flatten();
+                if let (Some(region), Some(end), Some(addr)) = (reserved, end, addr) {
+                    if end != addr {
+                        collect(
+                            &mut errors,
+                            Err(Error::new_spanned(
+                                &region.size,
+                                format!(
+                                    "reserved region ends at offset {:#x}, but `{}` is mapped to {:#x}",
+                                    end, layout.reg.ident, addr
+                                ),
+                            )),
+                        );
+                    }
+                }
+
+                end = addr
+                    .zip(register_size(&layout.reg))
+                    .map(|(addr, size)| addr + size);
+                reserved = None;
+            }
+        }
+    }
+
+    finish(errors)
+}
+
+fn validate_reserved(reserved: &Reserved) -> Result<()> {
+    if reserved.size()? == 0 {
+        return Err(Error::new_spanned(
+            &reserved.size,
+            "reserved regions must not be empty",
+        ));
+    }
+
+    Ok(())
+}
+
+/// The register types which implement `regi::Int` with their bit widths.
+const SUPPORTED_TYPES: &[(&str, usize)] = &[
+    ("u8", 8),
+    ("u16", 16),
+    ("u32", 32),
+    ("u64", 64),
+    ("u128", 128),
+];
+
+/// The primitive types which don't implement `regi::Int`.
+const UNSUPPORTED_PRIMITIVES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "usize", "f32", "f64", "bool", "char", "str",
+];
+
+fn validate_register(reg: &RegisterDef) -> Result<()> {
+    let mut errors = None;
+    collect(&mut errors, validate_type(&reg.ty));
+    if let Some(len) = &reg.len {
+        collect(&mut errors, validate_len(len));
+    }
+    if let Some(reset) = &reg.reset {
+        collect(&mut errors, validate_reset(reset, type_bits(&reg.ty)));
+    }
+    for field in &reg.fields {
+        collect(&mut errors, validate_field(field));
+    }
+    collect(&mut errors, validate_layout(reg));
+    collect(&mut errors, validate_permissions(reg));
+
+    finish(errors)
+}
+
+/// Checks that registers with "write 1 to clear" fields don't contain
+/// any other writable fields.
+///
+/// Writing the other fields would need a read-modify-write which writes
+/// back and thereby acknowledges all pending flags.
+fn validate_permissions(reg: &RegisterDef) -> Result<()> {
+    let has_w1c = reg
+        .fields
+        .iter()
+        .any(|field| matches!(field.permission, Permission::WriteOneToClear));
+    if !has_w1c {
+        return Ok(());
+    }
+
+    let mut errors = None;
+    for field in &reg.fields {
+        if matches!(field.permission, Permission::Write | Permission::ReadWrite) {
+            collect(
+                &mut errors,
+                Err(Error::new_spanned(
+                    &field.ident,
+                    format!(
+                        "field `{}` must not be writable, as `{}` has `w1c` fields \
+                         which only allow `r`, `rc` and `w1c` fields next to them",
+                        field.ident, reg.ident
+                    ),
+                )),
+            );
+        }
+    }
+
+    finish(errors)
+}
+
+fn validate_layout(reg: &RegisterDef) -> Result<()> {
+    let mut errors = None;
+
+    let reg_bits = type_bits(&reg.ty);
+    let mut claimed: Vec<(&BitField, Range<usize>)> = Vec::new();
+    for field in &reg.fields {
+        let bits = match field_bits(field, reg_bits) {
+            Ok(Some(bits)) => bits,
+            Ok(None) => continue,
+            Err(e) => {
+                collect(&mut errors, Err(e));
+                continue;
+            }
+        };
+
+        if let Some(reg_bits) = reg_bits {
+            if bits.start >= reg_bits || bits.end > reg_bits {
+                collect(
+                    &mut errors,
+                    Err(Error::new_spanned(
+                        &field.ident,
+                        format!(
+                            "field `{}` exceeds the {} bits of register `{}`",
+                            field.ident, reg_bits, reg.ident
+                        ),
+                    )),
+                );
+
+                // Overlaps with a misplaced field are not meaningful.
+                continue;
+            }
+        }
+
+        let previous = claimed
+            .iter()
+            .find(|(_, other)| bits.start < other.end && other.start < bits.end);
+        if let Some((other, _)) = previous {
+            collect(
+                &mut errors,
+                Err(Error::new_spanned(
+                    &field.ident,
+                    format!(
+                        "field `{}` overlaps with field `{}`",
+                        field.ident, other.ident
+                    ),
+                )),
+            );
+        }
+
+        claimed.push((field, bits));
+    }
+
+    finish(errors)
+}
+
+/// Resolves the bits a field covers in a register of `reg_bits` bits.
+///
+/// Returns `None` if the field extends to the end of a register of
+/// unknown width.
+fn field_bits(field: &BitField, reg_bits: Option<usize>) -> Result<Option<Range<usize>>> {
+    let start = field.range.start()?;
+    let end = match field.range.end()? {
+        Some(width) => start + width,
+        None => match reg_bits {
+            Some(bits) => bits.max(start),
+            None => return Ok(None),
+        },
+    };
+
+    Ok(Some(start..end))
+}
+
+fn validate_len(len: &syn::LitInt) -> Result<()> {
+    if len.base10_parse::<usize>()? == 0 {
+        return Err(Error::new_spanned(len, "register arrays must not be empty"));
+    }
+
+    Ok(())
+}
+
+/// Gets the bit width of a supported register type.
+///
+/// Type aliases and custom `Int` types can't be resolved here and are
+/// reported as `None`.
+fn type_bits(ty: &syn::Type) -> Option<usize> {
+    let ident = primitive_name(ty)?;
+    SUPPORTED_TYPES
+        .iter()
+        .find(|(name, _)| ident == name)
+        .map(|&(_, bits)| bits)
+}
+
+/// Gets the name of a type which refers to a primitive, either directly
+/// or through the `core::primitive` and `std::primitive` modules.
+fn primitive_name(ty: &syn::Type) -> Option<&syn::Ident> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    if let Some(ident) = path.get_ident() {
+        return Some(ident);
+    }
+
+    let segments: Vec<_> = path.segments.iter().collect();
+    match segments[..] {
+        [krate, module, ty]
+            if (krate.ident == "core" || krate.ident == "std")
+                && module.ident == "primitive"
+                && segments.iter().all(|s| s.arguments.is_empty()) =>
+        {
+            Some(&ty.ident)
+        }
+        _ => None,
+    }
+}
+
+/// Gets the size of a register in bytes, spanning all elements if it
+/// is an array.
+fn register_size(reg: &RegisterDef) -> Option<usize> {
+    let len = match &reg.len {
+        Some(len) => len.base10_parse().ok()?,
+        None => 1,
+    };
+
+    Some(type_bits(&reg.ty)? / 8 * len)
+}
+
+fn validate_reset(reset: &syn::LitInt, reg_bits: Option<usize>) -> Result<()> {
+    let value = reset.base10_parse::<u128>()?;
+    match reg_bits {
+        Some(bits) if bits < 128 && value >> bits != 0 => Err(Error::new_spanned(
+            reset,
+            format!("reset value does not fit into {} bits", bits),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn validate_type(ty: &syn::Type) -> Result<()> {
+    // Only primitives are known not to implement `regi::Int`; aliases
+    // and other types are left for the compiler to check.
+    match primitive_name(ty) {
+        Some(ident) if UNSUPPORTED_PRIMITIVES.iter().any(|name| ident == name) => {}
+        _ => return Ok(()),
+    }
+
+    let names: Vec<_> = SUPPORTED_TYPES.iter().map(|&(name, _)| name).collect();
+    Err(Error::new_spanned(
+        ty,
+        format!(
+            "unsupported register type, expected one of: {}",
+            names.join(", ")
+        ),
+    ))
+}
+
+fn validate_field(field: &BitField) -> Result<()> {
+    // Values of a write-only field can never be read back, so
+    // an enum to decode them is almost certainly a mistake.
+    if let (Permission::Write, Some(options)) = (&field.permission, &field.options) {
+        return Err(Error::new_spanned(
+            &options.ident,
+            "enum options are only supported on readable (`r`/`rw`/`rc`/`w1c`) fields",
+        ));
+    }
+
+    match &field.options {
+        Some(options) => validate_options(options),
+        None => Ok(()),
+    }
+}
+
+fn validate_options(options: &FieldOptions) -> Result<()> {
+    let mut errors = None;
+
+    // Only integer literals are compared; other expressions cannot be
+    // evaluated here and are left to the compiler.
+    let values: Vec<_> = options
+        .discriminants
+        .iter()
+        .map(|(ident, expr)| (ident, expr, literal_value(expr)))
+        .collect();
+
+    for (i, (ident, expr, value)) in values.iter().enumerate() {
+        let value = match value {
+            Some(value) => value,
+            None => continue,
+        };
+
+        let previous = values[..i]
+            .iter()
+            .find(|(_, _, other)| other.as_ref() == Some(value));
+        if let Some((other, _, _)) = previous {
+            collect(
+                &mut errors,
+                Err(Error::new_spanned(
+                    expr,
+                    format!("discriminant of `{}` is already used by `{}`", ident, other),
+                )),
+            );
+        }
+    }
+
+    finish(errors)
+}
+
+fn literal_value(expr: &syn::Expr) -> Option<u128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+/// Merges the error of `result`, if any, into `errors`.
+fn collect(errors: &mut Option<Error>, result: Result<()>) {
+    if let Err(e) = result {
+        match errors {
+            Some(errors) => errors.combine(e),
+            None => *errors = Some(e),
+        }
+    }
+}
+
+fn finish(errors: Option<Error>) -> Result<()> {
+    errors.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> RegisterBlock {
+        syn::parse_str(input).unwrap()
+    }
+
+    #[test]
+    fn test_enum_on_readable_field() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    rw MODE: 0..=1 = enum Mode { Slow 0, Fast 1 },
+                    r STATE: 2..4 = enum State { Idle 0, Busy 1 },
+                },
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_enum_on_write_only_field() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    w MODE: 0..=1 = enum Mode { Slow 0, Fast 1 },
+                },
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+        assert!(err.to_string().contains("readable"));
+    }
+
+    #[test]
+    fn test_supported_register_types() {
+        let block = parse(
+            "pub Dev {
+                0x0 => pub A as u8 {},
+                0x4 => pub B as u16 {},
+                0x8 => pub C as u32 {},
+                0x10 => pub D as u64 {},
+                0x20 => pub E as u128 {},
+                0x30 => pub F as core::primitive::u32 {},
+                0x34 => pub G as ::core::primitive::u32 {},
+                0x38 => pub H as std::primitive::u16 {},
+                0x3C => pub I as Word {},
+                0x40 => pub J as crate::Word {},
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_register_type() {
+        let block = parse(
+            "pub Dev {
+                0x0 => pub A as bool {},
+                0x4 => pub B as core::primitive::i32 {},
+                0x8 => pub C as ::std::primitive::usize {},
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+        assert_eq!(err.into_iter().count(), 3);
+
+        let block = parse("pub Dev { 0x0 => pub A as i32 {}, }");
+        let err = validate_block(&block).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported register type, expected one of: u8, u16, u32, u64, u128"
+        );
+    }
+
+    #[test]
+    fn test_unique_discriminants() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    rw MODE: 0..=1 = enum Mode { Slow 0, Medium 0b1, Fast 0x2, Turbo 3 },
+                },
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_discriminants() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    rw MODE: 0..=1 = enum Mode { Slow 0, Fast 1, Turbo 0x1, Idle 0 },
+                },
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "discriminant of `Turbo` is already used by `Fast`",
+                "discriminant of `Idle` is already used by `Slow`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_register_array() {
+        let block = parse(
+            "pub Nvic {
+                0x100 => pub PRIORITY[16] as u32 {
+                    rw LEVEL: 0..8,
+                },
+            }",
+        );
+        assert_eq!(
+            block
+                .registers()
+                .next()
+                .unwrap()
+                .reg
+                .len
+                .as_ref()
+                .unwrap()
+                .to_string(),
+            "16"
+        );
+        assert!(validate_block(&block).is_ok());
+
+        let block = parse("pub Nvic { 0x100 => pub PRIORITY[0] as u32 {}, }");
+        let err = validate_block(&block).unwrap_err();
+        assert!(err.to_string().contains("must not be empty"));
+    }
+
+    #[test]
+    fn test_reserved_region() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {},
+                _ => reserved(0xC),
+                0x10 => pub DATA as u32 {},
+            }",
+        );
+        assert_eq!(block.entries.len(), 3);
+        assert_eq!(block.registers().count(), 2);
+        assert!(validate_block(&block).is_ok());
+
+        let block = parse("pub Uart { _ => reserved(0), }");
+        let err = validate_block(&block).unwrap_err();
+        assert!(err.to_string().contains("must not be empty"));
+
+        let block = parse(
+            "pub Nvic {
+                _ => reserved(0x100),
+                0x100 => pub PRIORITY[16] as u32 {},
+                _ => reserved(0x10),
+                _ => reserved(0x30),
+                0x180 => pub TARGET as u8 {},
+                _ => reserved(0x7F),
+                BASE => pub TRIGGER as u32 {},
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+
+        assert!(syn::parse_str::<RegisterBlock>("pub Uart { _ => padding(4), }").is_err());
+    }
+
+    #[test]
+    fn test_reserved_region_mismatch() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {},
+                _ => reserved(0x8),
+                0x10 => pub DATA as u32 {},
+                0x14 => pub FIFO[2] as u16 {},
+                _ => reserved(0x4),
+                0x20 => pub STATUS as u32 {},
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "reserved region ends at offset 0xc, but `DATA` is mapped to 0x10",
+                "reserved region ends at offset 0x1c, but `STATUS` is mapped to 0x20",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_adjacent_fields() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u8 {
+                    rw MODE: 0..=1,
+                    rw SPEED: 2..4,
+                    r READY: 4,
+                    rw DATA: 5..,
+                },
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_overlapping_fields() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    rw MODE: 0..=3,
+                    rw SPEED: 3..6,
+                    r READY: 5,
+                    rw DATA: 8..,
+                    rw HIGH: 31,
+                },
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "field `SPEED` overlaps with field `MODE`",
+                "field `READY` overlaps with field `SPEED`",
+                "field `HIGH` overlaps with field `DATA`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_at_register_boundary() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u8 {
+                    rw LOW: 0..7,
+                    rw TOP: 7,
+                },
+                0x4 => pub DATA as u16 {
+                    rw VALUE: 8..=15,
+                },
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_field_exceeds_register() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u8 {
+                    rw BITS: 4..12,
+                    rw TOP: 8,
+                    rw REST: 8..,
+                },
+                0x4 => pub DATA as u16 {
+                    rw VALUE: 8..=16,
+                },
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "field `BITS` exceeds the 8 bits of register `CTRL`",
+                "field `TOP` exceeds the 8 bits of register `CTRL`",
+                "field `REST` exceeds the 8 bits of register `CTRL`",
+                "field `VALUE` exceeds the 16 bits of register `DATA`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_addresses() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {},
+                0x4 => pub STATUS as u32 {},
+                0 => pub DATA as u32 {},
+                0x4 => pub FIFO as u32 {},
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "register `DATA` is mapped to the same address as `CTRL`",
+                "register `FIFO` is mapped to the same address as `STATUS`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_addresses() {
+        let block = parse(
+            "pub Nvic {
+                0x0 => pub CTRL as u64 {},
+                0x4 => pub STATUS as u32 {},
+                0x8 => pub DATA as u8 {},
+                0x9 => pub FLAGS as u8 {},
+                0x100 => pub PRIORITY[16] as u32 {},
+                0x13C => pub LAST as u32 {},
+                0x140 => pub TARGET as u16 {},
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "register `STATUS` at 0x4..0x8 overlaps `CTRL` at 0x0..0x8",
+                "register `LAST` at 0x13c..0x140 overlaps `PRIORITY` at 0x100..0x140",
+            ]
+        );
+
+        let block = parse(
+            "pub Nvic {
+                0x100 => pub PRIORITY[16] as u32 {},
+                0x140 => pub TARGET as u16 {},
+                0x142 => pub MASK as u8 {},
+                0x143 => pub PENDING as u8 {},
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_reset_value() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 = reset(0x0000_0001) {
+                    rw ENABLE: 0,
+                },
+                0x4 => pub DATA as u8 = reset(0xFF) {},
+                0x8 => pub FIFO as u32 {},
+            }",
+        );
+        let resets: Vec<_> = block
+            .registers()
+            .map(|layout| {
+                layout
+                    .reg
+                    .reset
+                    .as_ref()
+                    .map(|lit| lit.base10_parse::<u32>().unwrap())
+            })
+            .collect();
+        assert_eq!(resets, [Some(1), Some(0xFF), None]);
+        assert!(validate_block(&block).is_ok());
+
+        let block = parse("pub Uart { 0x0 => pub DATA as u8 = reset(0x100) {}, }");
+        let err = validate_block(&block).unwrap_err();
+        assert_eq!(err.to_string(), "reset value does not fit into 8 bits");
+
+        assert!(
+            syn::parse_str::<RegisterBlock>("pub Uart { 0x0 => pub DATA as u8 = 0 {}, }").is_err()
+        );
+    }
+
+    #[test]
+    fn test_permissions() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    r PENDING: 0,
+                    w TRIGGER: 1,
+                    rw MASK: 2,
+                    rc OVERRUN: 4,
+                },
+                0x4 => pub IRQ as u32 {
+                    r PENDING: 0,
+                    w1c RX: 3,
+                    rc OVERRUN: 4,
+                },
+            }",
+        );
+        assert!(validate_block(&block).is_ok());
+
+        let block = parse(
+            "pub Uart {
+                0x0 => pub IRQ as u32 {
+                    w TRIGGER: 1,
+                    rw MASK: 2,
+                    w1c RX: 3,
+                },
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "field `TRIGGER` must not be writable, as `IRQ` has `w1c` fields \
+                 which only allow `r`, `rc` and `w1c` fields next to them",
+                "field `MASK` must not be writable, as `IRQ` has `w1c` fields \
+                 which only allow `r`, `rc` and `w1c` fields next to them",
+            ]
+        );
+
+        let err =
+            syn::parse_str::<RegisterBlock>("pub Uart { 0x0 => pub IRQ as u32 { x RX: 0 }, }")
+                .err()
+                .unwrap();
+        assert!(err.to_string().contains("w1c"));
+    }
+
+    #[test]
+    fn test_expression_addresses() {
+        let block = parse(
+            "pub Uart {
+                BASE => pub CTRL as u32 {},
+                BASE + 0x4 => pub STATUS as u32 {},
+                OFFSET * 4 => pub DATA as u32 {},
+                0x10 => pub FIFO as u32 {},
+            }",
+        );
+        let addresses: Vec<_> = block
+            .registers()
+            .map(|layout| layout.address().unwrap())
+            .collect();
+        assert_eq!(addresses, [None, None, None, Some(0x10)]);
+        assert!(validate_block(&block).is_ok());
+
+        let block = parse("pub Uart { \"CTRL\" => pub CTRL as u32 {}, }");
+        assert!(validate_block(&block).is_err());
+    }
+
+    #[test]
+    fn test_empty_field_range() {
+        let block = parse(
+            "pub Uart {
+                0x0 => pub CTRL as u32 {
+                    rw EMPTY: 2..2,
+                    rw DESCENDING: 4..3,
+                    rw SINGLE: 5..=5,
+                    rw REST: 6..,
+                },
+            }",
+        );
+        let err = validate_block(&block).unwrap_err();
+
+        let messages: Vec<_> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "range is empty, fields must be at least one bit wide",
+                "end of range must not be smaller than start of range",
+            ]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Timed register write sequences.
+//!
+//! Some peripherals must be configured in several steps with a minimum
+//! delay in between, e.g. when enabling a PLL and waiting for it to lock.
+//! This module drives such sequences with a delay provider from
+//! [`embedded_hal`].
+
+use embedded_hal::delay::DelayNs;
+
+use crate::{field::FieldValue, register::RegisterWrite};
+
+/// Writes each [`FieldValue`] of `steps` to `reg` in order, waiting for
+/// the number of nanoseconds paired with it after each write.
+///
+/// Every write is a direct [`RegisterWrite::write`] and thus overrides
+/// the whole register.
+pub fn write_sequence<W, D, S>(reg: &mut W, delay: &mut D, steps: S)
+where
+    W: RegisterWrite,
+    D: DelayNs,
+    S: IntoIterator<Item = (FieldValue<W::Register, W::Marker>, u32)>,
+{
+    for (value, ns) in steps {
+        reg.write(value);
+        delay.delay_ns(ns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::{field::Field, mmio::RegisterWindow, perms::ReadWrite};
+
+    #[derive(Default)]
+    struct CountingDelay {
+        calls: usize,
+        total_ns: u64,
+    }
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.calls += 1;
+            self.total_ns += u64::from(ns);
+        }
+    }
+
+    #[test]
+    fn test_write_sequence() {
+        const MODE: Field<u32, ReadWrite, ()> = Field::new(0b11, 0);
+
+        let cell = Cell::new(0);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+        let mut delay = CountingDelay::default();
+
+        write_sequence(
+            &mut reg,
+            &mut delay,
+            [(MODE.make_value(0b01), 1_000), (MODE.make_value(0b11), 500)],
+        );
+
+        assert_eq!(delay.calls, 2);
+        assert_eq!(delay.total_ns, 1_500);
+        assert_eq!(cell.get(), 0b11);
+    }
+}
@@ -8,15 +8,35 @@
 //! - [`FieldValue`] stores a concrete value along with the information on
 //!   how to correctly encode said value into the register.
 //!
+//! Logical values which are scattered across several disjoint bit ranges
+//! of a register are described by [`SplitField`]s instead.
+//!
 //! Both types can be used with supported unsigned primitive integer types
 //! and permissions provided by [`crate::perms`].
+//!
+//! # Constant evaluation
+//!
+//! Methods on [`Field`] and [`FieldValue`] that are generic over [`Int`]
+//! cannot be called in `const` contexts. Every operation therefore has a
+//! `const_`-prefixed counterpart for each concrete integer type which is
+//! guaranteed to fold into a constant, e.g. [`Field::const_read`]. This
+//! also applies to [`Field::new`], [`Field::make_value`],
+//! [`FieldValue::into_inner`] and [`FieldValue::const_modify`].
+//!
+//! Generic `const` access, e.g. a `const fn` reading a [`Field`] over
+//! any `I: Int`, would need the bit operations of [`Int`] to be callable
+//! in `const` contexts. That requires the unstable `const_trait_impl`
+//! feature, whose syntax for `const` traits and bounds still changes
+//! between nightlies, so it is not offered until const traits are stable.
+//! Generic code which needs constants has to be instantiated per integer
+//! type, e.g. through a macro, and call the `const_` methods.
 
-use core::{marker::PhantomData, ops};
+use core::{fmt, marker::PhantomData, ops, ops::Range};
 
 use crate::{
     perms::{self, Permission},
-    register::RegisterMarker,
-    sealed::Sealed,
+    register::{RegisterMarker, RegisterRead},
+    sealed::SealedInt,
     Int,
 };
 
@@ -42,10 +62,10 @@ pub struct Field<I, P, R> {
 ///
 /// Instances of this type should usually be obtained through
 /// [`Field::make_value`].
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy)]
 pub struct FieldValue<I, R> {
-    mask: I,
-    value: I,
+    pub(crate) mask: I,
+    pub(crate) value: I,
 
     __reg: PhantomData<R>,
 }
@@ -63,6 +83,15 @@ impl<I: Int, P: Permission, R: RegisterMarker> Field<I, P, R> {
         }
     }
 
+    /// Constructs a field which covers all bits of the register.
+    ///
+    /// This allows reading or replacing the full register word through
+    /// the regular field APIs.
+    #[inline]
+    pub fn whole() -> Self {
+        Self::new(!I::ZERO, 0)
+    }
+
     /// Reads the specified bits of this field out of the given
     /// `value`.
     #[inline]
@@ -89,9 +118,226 @@ impl<I: Int, P: Permission, R: RegisterMarker> Field<I, P, R> {
     pub fn is_set(self, value: I) -> bool {
         value & (self.mask << self.shift) != I::ZERO
     }
+
+    /// Checks if the specified bits of this field are clear in
+    /// the given `value`.
+    ///
+    /// This returns `true` if *all* of the specified field bits
+    /// are zero, `false` otherwise.
+    #[inline]
+    pub fn is_clear(self, value: I) -> bool {
+        !self.is_set(value)
+    }
+
+    /// Finds the lowest bit of this field which is set in the given
+    /// `value`.
+    ///
+    /// The returned index is relative to the start of the field, or
+    /// `None` if the field is clear.
+    #[inline]
+    pub fn first_set_index(self, value: I) -> Option<usize> {
+        let bits = self.read(value);
+        if bits == I::ZERO {
+            None
+        } else {
+            Some(bits.trailing_zeros() as usize)
+        }
+    }
+
+    /// Gets the width of this field in bits.
+    ///
+    /// This only inspects the encoding of the field.
+    #[inline]
+    pub fn width(self) -> u32 {
+        self.mask.count_ones()
+    }
+
+    /// Gets the offset of the lowest bit of this field in the register.
+    ///
+    /// This only inspects the encoding of the field.
+    #[inline]
+    pub fn offset(self) -> usize {
+        self.shift
+    }
+
+    /// Gets the range of bits in a register covered by this field.
+    #[inline]
+    pub fn range(self) -> Range<usize> {
+        self.offset()..self.offset() + self.width() as usize
+    }
+
+    /// Reads a single-bit field out of the given `value` as a `bool`.
+    ///
+    /// In debug builds, this asserts that the field is one bit wide.
+    #[inline]
+    pub fn read_bool(self, value: I) -> bool {
+        debug_assert_eq!(self.mask.count_ones(), 1, "field must be a single bit");
+        self.is_set(value)
+    }
+
+    /// Constructs a [`FieldValue`] which sets or clears a single-bit
+    /// field.
+    ///
+    /// In debug builds, this asserts that the field is one bit wide.
+    #[inline]
+    pub fn bool_value(self, set: bool) -> FieldValue<I, R>
+    where
+        P: perms::Writable,
+    {
+        debug_assert_eq!(self.mask.count_ones(), 1, "field must be a single bit");
+
+        let mask = self.mask << self.shift;
+        FieldValue::from_parts(mask, if set { mask } else { I::ZERO })
+    }
+
+    /// Reads this field out of `reg` and converts the raw value into
+    /// a typed representation `E`, such as a field enum.
+    ///
+    /// Raw values which do not convert into `E` are returned in `Err`
+    /// so that undefined encodings are surfaced to the caller.
+    #[inline]
+    pub fn read_as<E, T>(self, reg: &mut T) -> Result<E, I>
+    where
+        P: perms::Readable,
+        E: TryFrom<I>,
+        T: RegisterRead<Register = I, Marker = R>,
+    {
+        let raw = reg.read(self);
+        E::try_from(raw).map_err(|_| raw)
+    }
+
+    /// Counts the bits of this field that are set in the given
+    /// `value`.
+    #[inline]
+    pub fn popcount(self, value: I) -> u32 {
+        self.select(value).count_ones()
+    }
+
+    /// Reads the specified bits of this field out of the register
+    /// value at `index` in a captured `dump` of register values.
+    ///
+    /// This allows decoding register state that was obtained in bulk,
+    /// e.g. through DMA, without live hardware access.
+    ///
+    /// Returns `None` if `index` is out of bounds for `dump`.
+    #[inline]
+    pub fn read_from(self, dump: &[I], index: usize) -> Option<I> {
+        dump.get(index).map(|&value| self.read(value))
+    }
+
+    /// Checks if the specified bits of this field are set in the
+    /// register value at `index` in a captured `dump` of register
+    /// values.
+    ///
+    /// Returns `None` if `index` is out of bounds for `dump`.
+    #[inline]
+    pub fn is_set_in(self, dump: &[I], index: usize) -> Option<bool> {
+        dump.get(index).map(|&value| self.is_set(value))
+    }
+}
+
+/// Pinpoints a bit field which is split across non-contiguous bits of
+/// a register.
+///
+/// Unlike [`Field`], the `mask` of a [`SplitField`] is given in place,
+/// i.e. its bits are exactly the register bits that make up the field.
+///
+/// # Encoding
+///
+/// The bits of the field are compacted into a dense value in order of
+/// significance: the lowest set bit of the mask becomes bit 0 of the
+/// dense value, the next set bit becomes bit 1, and so on. A field made
+/// of register bits `[1:0]` and `[7:5]` thus yields a 5-bit value where
+/// bits `[1:0]` come from register bits `[1:0]` and bits `[4:2]` come
+/// from register bits `[7:5]`.
+#[derive(Debug)]
+pub struct SplitField<I, P, R> {
+    pub(crate) mask: I,
+
+    __perm: PhantomData<P>,
+    __reg: PhantomData<R>,
+}
+
+impl<I: Int, P: Permission, R: RegisterMarker> SplitField<I, P, R> {
+    /// Constructs a new split field from the register bits it covers.
+    #[inline]
+    pub const fn new(mask: I) -> Self {
+        Self {
+            mask,
+
+            __perm: PhantomData,
+            __reg: PhantomData,
+        }
+    }
+
+    /// Gathers the bits of this field out of the given `value` and
+    /// compacts them into a dense value.
+    #[inline]
+    pub fn read(self, value: I) -> I {
+        let one = I::low_mask(1);
+
+        let mut result = I::ZERO;
+        let mut remaining = self.mask;
+        let mut bit = 0;
+        while remaining != I::ZERO {
+            let pos = remaining.trailing_zeros() as usize;
+            if (value >> pos) & one != I::ZERO {
+                result = result | (one << bit);
+            }
+
+            remaining = remaining & !(one << pos);
+            bit += 1;
+        }
+
+        result
+    }
+
+    /// Constructs a [`FieldValue`] by scattering the bits of a dense
+    /// `value` out to the register bits of this field.
+    ///
+    /// Bits of `value` beyond the [width][SplitField::width] of the
+    /// field are ignored.
+    #[inline]
+    pub fn make_value(self, value: I) -> FieldValue<I, R>
+    where
+        P: perms::Writable,
+    {
+        let one = I::low_mask(1);
+
+        let mut result = I::ZERO;
+        let mut remaining = self.mask;
+        let mut bit = 0;
+        while remaining != I::ZERO {
+            let pos = remaining.trailing_zeros() as usize;
+            if (value >> bit) & one != I::ZERO {
+                result = result | (one << pos);
+            }
+
+            remaining = remaining & !(one << pos);
+            bit += 1;
+        }
+
+        FieldValue::from_parts(self.mask, result)
+    }
+
+    /// Gets the width of the dense value of this field in bits.
+    #[inline]
+    pub fn width(self) -> u32 {
+        self.mask.count_ones()
+    }
 }
 
 impl<I: Int, R: RegisterMarker> FieldValue<I, R> {
+    #[inline]
+    pub(crate) const fn from_parts(mask: I, value: I) -> Self {
+        Self {
+            mask,
+            value,
+
+            __reg: PhantomData,
+        }
+    }
+
     /// Consumes the [`FieldValue`], returning the integer value it stores.
     #[inline]
     pub const fn into_inner(self) -> I {
@@ -102,13 +348,51 @@ impl<I: Int, R: RegisterMarker> FieldValue<I, R> {
     /// returns the resulting updated value.
     #[inline]
     pub fn modify(self, new: I) -> I {
-        (new & !self.mask) | self.mask
+        (new & !self.mask) | self.value
+    }
+
+    /// Checks if the bits of the described field in `value` are equal
+    /// to the wrapped value.
+    ///
+    /// All bits outside of the field are ignored.
+    #[inline]
+    pub fn matches(self, value: I) -> bool {
+        value & self.mask == self.value
+    }
+}
+
+/// The empty value, which covers no bits of the register.
+///
+/// Values for individual fields can be accumulated onto it.
+impl<I: Int, R: RegisterMarker> Default for FieldValue<I, R> {
+    #[inline]
+    fn default() -> Self {
+        Self::from_parts(I::ZERO, I::ZERO)
+    }
+}
+
+/// Formats the mask and the value in hexadecimal.
+impl<I: Int + fmt::LowerHex, R: RegisterMarker> fmt::Debug for FieldValue<I, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldValue")
+            .field("mask", &format_args!("{:#x}", self.mask))
+            .field("value", &format_args!("{:#x}", self.value))
+            .finish()
     }
 }
 
 macro_rules! impl_field_for {
     ($ty:ty) => {
         impl<P: Permission, R: RegisterMarker> Field<$ty, P, R> {
+            /// Constructs a field which covers all bits of the register.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_whole() -> Self {
+                Self::new(<$ty>::MAX, 0)
+            }
+
             /// Reads the specified bits of this field out of the given
             /// `value`.
             ///
@@ -119,6 +403,25 @@ macro_rules! impl_field_for {
                 (value & (self.mask << self.shift)) >> self.shift
             }
 
+            /// Gets the range of bits in a register covered by this field.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_range(self) -> Range<usize> {
+                self.shift..self.shift + self.mask.count_ones() as usize
+            }
+
+            /// Selects the bits of this field in the given `value` and
+            /// zeroes all other bits.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_select(self, value: $ty) -> $ty {
+                value & (self.mask << self.shift)
+            }
+
             /// Checks if the specified bits of this field are set in the
             /// given `value`.
             ///
@@ -132,6 +435,45 @@ macro_rules! impl_field_for {
                 value & (self.mask << self.shift) != 0
             }
 
+            /// Checks if the specified bits of this field are clear in the
+            /// given `value`.
+            ///
+            /// This returns `true` if *all* of the specified field bits are
+            /// zero, `false` otherwise.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_is_clear(self, value: $ty) -> bool {
+                !self.const_is_set(value)
+            }
+
+            /// Finds the lowest bit of this field which is set in the
+            /// given `value`.
+            ///
+            /// The returned index is relative to the start of the field,
+            /// or `None` if the field is clear.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_first_set_index(self, value: $ty) -> Option<usize> {
+                match self.const_read(value) {
+                    0 => None,
+                    bits => Some(bits.trailing_zeros() as usize),
+                }
+            }
+
+            /// Counts the bits of this field that are set in the given
+            /// `value`.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_popcount(self, value: $ty) -> u32 {
+                (value & (self.mask << self.shift)).count_ones()
+            }
+
             /// Constructs a [`FieldValue`] from a concrete value, preserving
             /// the encoding information.
             ///
@@ -142,7 +484,38 @@ macro_rules! impl_field_for {
             where
                 P: perms::Writable,
             {
-                FieldValue::<$ty, R>::new(self.mask << self.shift, value)
+                FieldValue::<$ty, R>::new(self.mask << self.shift, value << self.shift)
+            }
+
+            /// Constructs a [`FieldValue`] from a concrete value like
+            /// [`Field::make_value`], but returns `None` if `value` does not
+            /// fit into the width of this field instead of truncating it.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn try_make_value(&self, value: $ty) -> Option<FieldValue<$ty, R>>
+            where
+                P: perms::Writable,
+            {
+                if value & !self.mask != 0 {
+                    None
+                } else {
+                    Some(self.make_value(value))
+                }
+            }
+
+            /// Constructs a [`FieldValue`] which clears all bits of this
+            /// field.
+            ///
+            /// This is the counterpart to [`Field::make_value`] and can be
+            /// used in `const fn`s.
+            #[inline]
+            pub const fn clear_value(&self) -> FieldValue<$ty, R>
+            where
+                P: perms::Writable,
+            {
+                FieldValue::<$ty, R>::new(self.mask << self.shift, 0)
             }
         }
 
@@ -166,6 +539,24 @@ macro_rules! impl_field_for {
             pub const fn const_modify(self, new: $ty) -> $ty {
                 (new & !self.mask) | self.value
             }
+
+            /// Checks if the bits of the described field in `value` are
+            /// equal to the wrapped value.
+            ///
+            /// This does not rely on [`Int`] generics and can therefore
+            /// be used in `const fn`s.
+            #[inline]
+            pub const fn const_matches(self, value: $ty) -> bool {
+                value & self.mask == self.value
+            }
+
+            /// Checks if this value does not cover any register bits.
+            ///
+            /// Writing such a value is a no-op and may be skipped.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.mask == 0
+            }
         }
 
         /// Lowers a field value into the primitive it wraps.
@@ -200,6 +591,63 @@ macro_rules! impl_field_for {
             }
         }
 
+        /// Merge already encoded register bits into a field value using
+        /// the `|` operator.
+        ///
+        /// The integer is taken as both the mask and the value of the
+        /// bits it contributes, so only its set bits are written. Use a
+        /// [`FieldValue`] instead to also clear bits.
+        impl<R: RegisterMarker> ops::BitOr<$ty> for FieldValue<$ty, R> {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, rhs: $ty) -> Self::Output {
+                Self {
+                    mask: self.mask | rhs,
+                    value: self.value | rhs,
+
+                    __reg: PhantomData,
+                }
+            }
+        }
+
+        /// Intersect two field values using the `&` operator.
+        ///
+        /// The result only covers the bits described by both masks and
+        /// keeps the value bits that are set in both operands.
+        impl<R: RegisterMarker> ops::BitAnd<FieldValue<$ty, R>> for FieldValue<$ty, R> {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, rhs: Self) -> Self::Output {
+                Self {
+                    mask: self.mask & rhs.mask,
+                    value: self.value & rhs.value,
+
+                    __reg: PhantomData,
+                }
+            }
+        }
+
+        /// Toggle the bits of one field value by another using the `^`
+        /// operator.
+        ///
+        /// The result covers the bits described by either mask, so bits
+        /// described by only one operand keep its value unchanged.
+        impl<R: RegisterMarker> ops::BitXor<FieldValue<$ty, R>> for FieldValue<$ty, R> {
+            type Output = Self;
+
+            #[inline]
+            fn bitxor(self, rhs: Self) -> Self::Output {
+                Self {
+                    mask: self.mask | rhs.mask,
+                    value: self.value ^ rhs.value,
+
+                    __reg: PhantomData,
+                }
+            }
+        }
+
         /// Direct comparison with the integer value stored in a field.
         impl<R: RegisterMarker> PartialEq<$ty> for FieldValue<$ty, R> {
             fn eq(&self, rhs: &$ty) -> bool {
@@ -213,11 +661,12 @@ impl_field_for!(u8);
 impl_field_for!(u16);
 impl_field_for!(u32);
 impl_field_for!(u64);
+impl_field_for!(u128);
 
 // `#[derive(Clone, Copy)]` does not produce the desired generic bounds.
 // See: https://github.com/rust-lang/rust/issues/26925
 
-impl<I: Sealed + Copy, P, R> Clone for Field<I, P, R> {
+impl<I: SealedInt + Copy, P, R> Clone for Field<I, P, R> {
     fn clone(&self) -> Self {
         Self {
             mask: self.mask,
@@ -228,4 +677,325 @@ impl<I: Sealed + Copy, P, R> Clone for Field<I, P, R> {
         }
     }
 }
-impl<I: Sealed + Copy, P, R> Copy for Field<I, P, R> {}
+impl<I: SealedInt + Copy, P, R> Copy for Field<I, P, R> {}
+
+impl<I: SealedInt + Copy, P, R> Clone for SplitField<I, P, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<I: SealedInt + Copy, P, R> Copy for SplitField<I, P, R> {}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::{mmio::RegisterWindow, perms::ReadWrite};
+
+    const NIBBLE: Field<u16, ReadWrite, ()> = Field::new(0xF, 4);
+
+    const _: () = assert!(NIBBLE.const_read(0xABCD) == 0xC);
+    const _: () = assert!(NIBBLE.const_select(0xABCD) == 0xC0);
+    const _: () = assert!(NIBBLE.const_is_set(0x0010));
+    const _: () = assert!(!NIBBLE.const_is_set(0xFF0F));
+    const _: () = assert!(NIBBLE.const_popcount(0x0050) == 2);
+    const _: () = assert!(NIBBLE.make_value(0xFF).into_inner() == 0xF0);
+    const _: () = assert!(NIBBLE.make_value(0xFF).const_modify(0x1234) == 0x12F4);
+    const _: () = assert!(!NIBBLE.make_value(0).is_empty());
+    const _: () = assert!(NIBBLE.make_value(0xC).const_matches(0xABCD));
+    const _: () = assert!(!NIBBLE.make_value(0xD).const_matches(0xABCD));
+    const _: () = assert!(NIBBLE.clear_value().const_modify(0xFF) == 0x0F);
+    const _: () = assert!(Field::<u16, ReadWrite, ()>::const_whole().const_read(0xABCD) == 0xABCD);
+
+    #[test]
+    fn test_field_decode_dump() {
+        const LOW: Field<u32, ReadWrite, ()> = Field::new(0xF, 0);
+        const HIGH: Field<u32, ReadWrite, ()> = Field::new(0xFF, 24);
+
+        let dump = [0x1200_0003, 0x0000_0000, 0xAB00_000F];
+
+        assert_eq!(LOW.read_from(&dump, 0), Some(0x3));
+        assert_eq!(HIGH.read_from(&dump, 0), Some(0x12));
+        assert_eq!(HIGH.read_from(&dump, 2), Some(0xAB));
+        assert_eq!(LOW.is_set_in(&dump, 1), Some(false));
+        assert_eq!(LOW.is_set_in(&dump, 2), Some(true));
+
+        assert_eq!(LOW.read_from(&dump, 3), None);
+        assert_eq!(HIGH.is_set_in(&dump, 3), None);
+    }
+
+    #[test]
+    fn test_field_popcount() {
+        let lanes = Field::<u8, ReadWrite, ()>::new(0b111, 2);
+        assert_eq!(lanes.popcount(0b0001_0100), 2);
+        assert_eq!(lanes.popcount(0b1110_0111), 1);
+        assert_eq!(lanes.const_popcount(0xFF), 3);
+
+        let channels = Field::<u16, ReadWrite, ()>::new(0xFF, 8);
+        assert_eq!(channels.popcount(0x00FF), 0);
+        assert_eq!(channels.popcount(0x8181), 2);
+
+        let word = Field::<u32, ReadWrite, ()>::new(0xFFFF_FFFF, 0);
+        assert_eq!(word.popcount(0xF0F0_F0F0), 16);
+        assert_eq!(word.const_popcount(u32::MAX), 32);
+
+        let high = Field::<u64, ReadWrite, ()>::new(0xF, 60);
+        assert_eq!(high.popcount(u64::MAX), 4);
+        assert_eq!(high.const_popcount(0x0FFF_FFFF_FFFF_FFFF), 0);
+    }
+
+    #[test]
+    fn test_field_bool() {
+        let busy = Field::<u32, ReadWrite, ()>::new(0b1, 31);
+        assert!(busy.read_bool(0x8000_0000));
+        assert!(!busy.read_bool(0x7FFF_FFFF));
+
+        let set = busy.bool_value(true);
+        assert_eq!(set.into_inner(), 0x8000_0000);
+        assert_eq!(set.const_modify(0x0000_00FF), 0x8000_00FF);
+
+        let clear = busy.bool_value(false);
+        assert_eq!(clear.into_inner(), 0);
+        assert_eq!(clear.const_modify(0xFFFF_FFFF), 0x7FFF_FFFF);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_field_bool_wide() {
+        Field::<u8, ReadWrite, ()>::new(0b11, 0).read_bool(0);
+    }
+
+    #[test]
+    fn test_field_width_offset() {
+        let flag = Field::<u8, ReadWrite, ()>::new(0b1, 7);
+        assert_eq!((flag.width(), flag.offset()), (1, 7));
+
+        let word = Field::<u64, ReadWrite, ()>::new(0xFFFF_FFFF, 16);
+        assert_eq!((word.width(), word.offset()), (32, 16));
+    }
+
+    #[test]
+    fn test_field_range() {
+        assert_eq!(Field::<u8, ReadWrite, ()>::new(0b1, 0).range(), 0..1);
+        assert_eq!(Field::<u16, ReadWrite, ()>::new(0xF, 4).range(), 4..8);
+        assert_eq!(
+            Field::<u32, ReadWrite, ()>::new(0xFFFF_FFFF, 0).range(),
+            0..32
+        );
+        assert_eq!(Field::<u64, ReadWrite, ()>::new(0x3, 62).range(), 62..64);
+        assert_eq!(NIBBLE.const_range(), 4..8);
+    }
+
+    #[test]
+    fn test_field_value_modify() {
+        let middle = Field::<u8, ReadWrite, ()>::new(0b111, 2);
+        let value = middle.make_value(0b010);
+
+        assert_eq!(value.into_inner(), 0b0000_1000);
+        assert_eq!(value.modify(0xFF), 0b1110_1011);
+        assert_eq!(value.modify(0xFF), value.const_modify(0xFF));
+    }
+
+    #[test]
+    fn test_field_is_clear() {
+        let mode = Field::<u32, ReadWrite, ()>::new(0b111, 8);
+
+        assert!(mode.is_clear(0xFFFF_F8FF));
+        assert!(mode.const_is_clear(0));
+        assert!(!mode.is_clear(0x0000_0200));
+        assert!(!mode.const_is_clear(0x0000_0700));
+    }
+
+    #[test]
+    fn test_field_first_set_index() {
+        let pending = Field::<u16, ReadWrite, ()>::new(0xFF, 4);
+
+        assert_eq!(pending.first_set_index(0xF00F), None);
+        assert_eq!(pending.first_set_index(0x0400), Some(6));
+        assert_eq!(pending.first_set_index(0x0FF0), Some(0));
+        assert_eq!(pending.const_first_set_index(0x0C00), Some(6));
+        assert_eq!(pending.const_first_set_index(0), None);
+    }
+
+    #[test]
+    fn test_split_field() {
+        // Bits [1:0] and [7:5].
+        const SPLIT: SplitField<u8, ReadWrite, ()> = SplitField::new(0b1110_0011);
+
+        assert_eq!(SPLIT.width(), 5);
+
+        assert_eq!(SPLIT.read(0b1010_0001), 0b10101);
+        assert_eq!(SPLIT.read(0b0001_1100), 0);
+        assert_eq!(SPLIT.read(0xFF), 0b11111);
+
+        let value = SPLIT.make_value(0b10101);
+        assert_eq!(value.into_inner(), 0b1010_0001);
+        assert_eq!(value.modify(0b0001_1110), 0b1011_1101);
+
+        // Bits beyond the width of the field are dropped.
+        assert_eq!(SPLIT.make_value(0xFF).into_inner(), 0b1110_0011);
+
+        for dense in 0..32 {
+            assert_eq!(SPLIT.read(SPLIT.make_value(dense).into_inner()), dense);
+        }
+    }
+
+    #[test]
+    fn test_field_value_is_empty() {
+        const LOW: Field<u32, ReadWrite, ()> = Field::new(0xF, 0);
+        const HIGH: Field<u32, ReadWrite, ()> = Field::new(0xF, 28);
+
+        let mut value = FieldValue::<u32, ()>::default();
+        assert!(value.is_empty());
+        assert_eq!(value.modify(0x1234_5678), 0x1234_5678);
+
+        value |= LOW.make_value(0);
+        assert!(!value.is_empty());
+
+        assert!(!(LOW.make_value(1) | HIGH.make_value(2)).is_empty());
+    }
+
+    #[test]
+    fn test_field_read_as() {
+        #[derive(Debug, PartialEq)]
+        enum Mode {
+            Slow,
+            Fast,
+        }
+
+        impl TryFrom<u32> for Mode {
+            type Error = ();
+
+            fn try_from(raw: u32) -> Result<Self, Self::Error> {
+                match raw {
+                    0 => Ok(Mode::Slow),
+                    1 => Ok(Mode::Fast),
+                    _ => Err(()),
+                }
+            }
+        }
+
+        const MODE: Field<u32, ReadWrite, ()> = Field::new(0b11, 4);
+
+        let cell = Cell::new(0x10);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+        assert_eq!(MODE.read_as::<Mode, _>(&mut reg), Ok(Mode::Fast));
+
+        cell.set(0x00);
+        assert_eq!(MODE.read_as::<Mode, _>(&mut reg), Ok(Mode::Slow));
+
+        cell.set(0x30);
+        assert_eq!(MODE.read_as::<Mode, _>(&mut reg), Err(0b11));
+    }
+
+    #[test]
+    fn test_field_value_matches() {
+        const LOW: Field<u32, ReadWrite, ()> = Field::new(0xF, 0);
+        const HIGH: Field<u32, ReadWrite, ()> = Field::new(0xF, 28);
+
+        assert!(LOW.make_value(0x8).matches(0x1234_5678));
+        assert!(!LOW.make_value(0x7).matches(0x1234_5678));
+        assert!(HIGH.make_value(0x1).matches(0x1234_5678));
+        assert!(LOW.make_value(0).matches(0xFFFF_FFF0));
+
+        // Every accumulated field has to match.
+        let both = LOW.make_value(0x8) | HIGH.make_value(0x1);
+        assert!(both.matches(0x1FFF_FFF8));
+        assert!(!both.matches(0x2000_0008));
+        assert!(!both.matches(0x1000_0009));
+
+        // The empty value matches anything.
+        assert!(FieldValue::<u32, ()>::default().matches(0xDEAD_BEEF));
+    }
+
+    #[test]
+    fn test_field_clear_value() {
+        const LOW: Field<u8, ReadWrite, ()> = Field::new(0xF, 0);
+
+        let value = LOW.clear_value();
+        assert!(!value.is_empty());
+        assert_eq!(value.into_inner(), 0);
+        assert_eq!(value.const_modify(0xFF), 0xF0);
+        assert!(value.matches(0xA0));
+        assert!(!value.matches(0xA1));
+    }
+
+    #[test]
+    fn test_field_try_make_value() {
+        const MODE: Field<u32, ReadWrite, ()> = Field::new(0b111, 8);
+
+        let value = MODE.try_make_value(0b111).unwrap();
+        assert_eq!(value.into_inner(), 0x700);
+
+        assert!(MODE.try_make_value(0).is_some());
+        assert!(MODE.try_make_value(0b1000).is_none());
+        assert!(MODE.try_make_value(0xFF).is_none());
+        assert!(MODE.try_make_value(u32::MAX).is_none());
+    }
+
+    #[test]
+    fn test_field_value_ops() {
+        const LOW: Field<u8, ReadWrite, ()> = Field::new(0xF, 0);
+        const MIDDLE: Field<u8, ReadWrite, ()> = Field::new(0xF, 2);
+
+        let low = LOW.make_value(0b1010);
+        let middle = MIDDLE.make_value(0b0110);
+
+        let and = low & middle;
+        assert_eq!(and.mask, 0b0000_1100);
+        assert_eq!(and.value, 0b0000_1000);
+
+        let xor = low ^ middle;
+        assert_eq!(xor.mask, 0b0011_1111);
+        assert_eq!(xor.value, 0b0001_0010);
+
+        let empty = FieldValue::<u8, ()>::default();
+        assert!((low & empty).is_empty());
+        assert_eq!((low ^ empty).value, low.value);
+    }
+
+    #[test]
+    fn test_field_value_or_raw() {
+        const LOW: Field<u16, ReadWrite, ()> = Field::new(0xF, 0);
+        const HIGH: Field<u16, ReadWrite, ()> = Field::new(0xF, 12);
+        const ENABLE: u16 = 1 << 8;
+
+        let value = LOW.make_value(0x5) | HIGH.make_value(0) | ENABLE;
+        assert_eq!(value.mask, 0xF10F);
+        assert_eq!(value.value, 0x0105);
+        assert_eq!(value.modify(0xFAF0), 0x0BF5);
+    }
+
+    #[test]
+    fn test_field_whole() {
+        let whole = Field::<u32, ReadWrite, ()>::whole();
+        assert_eq!(whole.width(), 32);
+        assert_eq!(whole.offset(), 0);
+        assert_eq!(whole.read(0xDEAD_BEEF), 0xDEAD_BEEF);
+
+        let value = whole.make_value(0x1234_5678);
+        assert_eq!(value.modify(0xFFFF_FFFF), 0x1234_5678);
+
+        let whole = Field::<u128, ReadWrite, ()>::whole();
+        assert_eq!(whole.read(u128::MAX - 1), u128::MAX - 1);
+    }
+
+    #[test]
+    fn test_field_value_debug() {
+        extern crate std;
+
+        const MODE: Field<u32, ReadWrite, ()> = Field::new(0b11, 4);
+
+        let value = MODE.make_value(0b10);
+        assert_eq!(
+            std::format!("{:?}", value),
+            "FieldValue { mask: 0x30, value: 0x20 }"
+        );
+        assert_eq!(
+            std::format!("{:?}", FieldValue::<u8, ()>::default()),
+            "FieldValue { mask: 0x0, value: 0x0 }"
+        );
+    }
+}
@@ -4,18 +4,33 @@
 #![feature(arbitrary_self_types, const_fn_trait_bound)]
 #![no_std]
 
-use core::ops::{BitAnd, BitOr, Not, Shl, Shr};
+use core::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
 
 pub use regi_impl::*;
 
+#[cfg(feature = "embedded-hal")]
+pub mod delay;
+
 pub mod field;
 
 pub mod mmio;
 
+#[cfg(feature = "async")]
+pub mod poll;
+
+#[cfg(feature = "heapless")]
+pub mod queue;
+
 pub mod perms;
 
+#[cfg(feature = "profile")]
+pub mod profile;
+
 pub mod register;
 
+#[cfg(feature = "critical-section")]
+pub mod shared;
+
 #[inline(always)]
 const fn is_aligned(value: usize, align: usize) -> bool {
     debug_assert!(align.is_power_of_two());
@@ -48,6 +63,47 @@ macro_rules! make_register_window {
     };
 }
 
+/// Builds a [`FieldValue`][crate::field::FieldValue] from several named
+/// fields of a register and their values.
+///
+/// The fields are looked up as constants in the module given before the
+/// braces, and each value is encoded with
+/// [`Field::make_value`][crate::field::Field::make_value] before all of
+/// them are combined with `|`.
+///
+/// # Example
+///
+/// ```
+/// # use regi::with_fields;
+/// mod ctrl {
+/// #   use regi::{field::Field, perms::ReadWrite};
+///     pub const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+///     pub const MODE: Field<u32, ReadWrite, ()> = Field::new(0b11, 1);
+/// }
+///
+/// let value = with_fields!(ctrl { ENABLE: 1, MODE: 0b10 });
+/// ```
+///
+/// Naming a field which is not declared for the register fails to
+/// compile:
+///
+/// ```compile_fail
+/// # use regi::with_fields;
+/// mod ctrl {
+/// #   use regi::{field::Field, perms::ReadWrite};
+///     pub const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+/// }
+///
+/// let value = with_fields!(ctrl { ENABLE: 1, SPEED: 3 });
+/// ```
+#[macro_export]
+macro_rules! with_fields {
+    ($($reg:ident)::+ { $($field:ident: $value:expr),+ $(,)? }) => {{
+        use $($reg)::+ as __fields;
+        $( __fields::$field.make_value($value) )|+
+    }};
+}
+
 /// Any integral type that can be used as for representation of underlying
 /// storage of registers.
 pub trait Int:
@@ -55,35 +111,380 @@ pub trait Int:
     + Copy
     + BitAnd<Output = Self>
     + BitOr<Output = Self>
+    + BitXor<Output = Self>
     + Not<Output = Self>
     + Shl<usize, Output = Self>
     + Shr<usize, Output = Self>
     + Default
     + PartialEq
-    + sealed::Sealed
+    + sealed::SealedInt
 {
     /// The value of `0` for this type.
     const ZERO: Self;
+
+    /// Returns the number of ones in the binary representation of `self`.
+    fn count_ones(self) -> u32;
+
+    /// Returns the number of trailing zeros in the binary representation
+    /// of `self`.
+    fn trailing_zeros(self) -> u32;
+
+    /// Builds a mask of the `width` least significant bits.
+    ///
+    /// A `width` equal to or exceeding the bit width of the type
+    /// saturates to a mask with all bits set.
+    fn low_mask(width: u32) -> Self;
+
+    /// Sign-extends the `width` least significant bits of `self` to the
+    /// full bit width of the type, treating bit `width - 1` as the sign.
+    ///
+    /// The result is the two's complement bit pattern of the value which
+    /// the caller may reinterpret as a signed integer. All bits above
+    /// `width` in `self` are ignored. A `width` of `0` yields `0` and a
+    /// `width` equal to or exceeding the bit width of the type returns
+    /// `self` unchanged.
+    fn sign_extend(self, width: u32) -> Self;
+
+    /// Losslessly widens `self` to a [`u128`].
+    fn widen_to_u128(self) -> u128;
+
+    /// Narrows a [`u128`] to this type.
+    ///
+    /// Returns `None` if `value` does not fit into the type.
+    fn narrow_from_u128(value: u128) -> Option<Self>;
+
+    /// Widens `self` to a [`u64`].
+    ///
+    /// This is lossless for all types of up to 64 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value of a wider type, e.g. [`u128`], does not fit
+    /// into a [`u64`]. Use [`Int::widen_to_u128`] for those.
+    #[inline]
+    fn widen_to_u64(self) -> u64 {
+        u64::try_from(self.widen_to_u128()).expect("value does not fit into u64")
+    }
+
+    /// Narrows a [`u64`] to this type.
+    ///
+    /// Returns `None` if `value` does not fit into the type.
+    #[inline]
+    fn narrow_from_u64(value: u64) -> Option<Self> {
+        Self::narrow_from_u128(value.into())
+    }
 }
 
 macro_rules! impl_int {
-    ($($ty:ty),*) => {
+    ($($ty:ty => $signed:ty),*) => {
         $(
             impl Int for $ty {
                 const ZERO: Self = 0;
+
+                #[inline(always)]
+                fn count_ones(self) -> u32 {
+                    <$ty>::count_ones(self)
+                }
+
+                #[inline(always)]
+                fn trailing_zeros(self) -> u32 {
+                    <$ty>::trailing_zeros(self)
+                }
+
+                #[inline(always)]
+                fn low_mask(width: u32) -> Self {
+                    if width >= <$ty>::BITS {
+                        <$ty>::MAX
+                    } else {
+                        (1 << width) - 1
+                    }
+                }
+
+                #[inline(always)]
+                fn sign_extend(self, width: u32) -> Self {
+                    match width {
+                        0 => 0,
+                        width if width >= <$ty>::BITS => self,
+                        width => {
+                            // Move the sign bit to the top, then let the
+                            // arithmetic right shift replicate it.
+                            let shift = <$ty>::BITS - width;
+                            (((self << shift) as $signed) >> shift) as $ty
+                        }
+                    }
+                }
+
+                #[inline(always)]
+                fn widen_to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                #[inline(always)]
+                fn narrow_from_u128(value: u128) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
             }
         )*
     };
 }
 
-impl_int!(u8, u16, u32, u64);
+impl_int!(u8 => i8, u16 => i16, u32 => i32, u64 => i64, u128 => i128);
+
+/// Escape hatch for implementing [`Int`] for custom storage types.
+///
+/// [`Int`] is sealed to the unsigned primitive integers. Implementing
+/// this trait for a type lifts that restriction, so that [`Int`] may be
+/// implemented for it manually, e.g. for a simulated register type.
+///
+/// Note that the `const_`-prefixed helpers of [`Field`][field::Field]
+/// and [`FieldValue`][field::FieldValue] are only available for the
+/// primitive integers.
+///
+/// Implementing this trait only lifts the seal on [`Int`]; all other
+/// sealed traits of this crate, such as the
+/// [permissions][crate::perms::Permission], remain closed:
+///
+/// ```compile_fail
+/// use regi::{perms::Permission, UnsafeIntImpl};
+///
+/// struct Forged;
+/// unsafe impl UnsafeIntImpl for Forged {}
+///
+/// impl Permission for Forged {
+///     const READABLE: bool = true;
+///     const WRITABLE: bool = true;
+/// }
+/// ```
+///
+/// # Stability
+///
+/// As this makes [`Int`] implementable outside of this crate, adding
+/// a required method to [`Int`] or changing the signature of one is a
+/// breaking change for such implementations.
+///
+/// # Safety
+///
+/// The type must behave exactly like an unsigned primitive integer of
+/// the same size: it must be plain data which can be copied with
+/// volatile reads and writes, [`Int::ZERO`] must have all bits clear,
+/// and all the operators and methods of [`Int`] must act on its bits
+/// like they do for the primitive integers.
+pub unsafe trait UnsafeIntImpl {}
 
 pub(crate) mod sealed {
+    /// Seals the traits of this crate, such as the permissions.
+    ///
+    /// This must never be implemented outside of this crate.
     pub trait Sealed {}
 
+    /// Seals [`Int`][super::Int] separately from [`Sealed`], so that
+    /// [`UnsafeIntImpl`][super::UnsafeIntImpl] opens up only [`Int`].
+    pub trait SealedInt {}
+
     // Impls for supported register value primitives.
-    impl Sealed for u8 {}
-    impl Sealed for u16 {}
-    impl Sealed for u32 {}
-    impl Sealed for u64 {}
+    impl SealedInt for u8 {}
+    impl SealedInt for u16 {}
+    impl SealedInt for u32 {}
+    impl SealedInt for u64 {}
+    impl SealedInt for u128 {}
+
+    // Custom storage types which opted into implementing `Int`.
+    impl<T: super::UnsafeIntImpl> SealedInt for T {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field::Field, perms::ReadWrite};
+
+    #[test]
+    fn test_int_low_mask() {
+        assert_eq!(u8::low_mask(0), 0);
+        assert_eq!(u8::low_mask(3), 0b111);
+        assert_eq!(u8::low_mask(8), u8::MAX);
+        assert_eq!(u8::low_mask(9), u8::MAX);
+
+        assert_eq!(u16::low_mask(0), 0);
+        assert_eq!(u16::low_mask(12), 0xFFF);
+        assert_eq!(u16::low_mask(16), u16::MAX);
+
+        assert_eq!(u32::low_mask(0), 0);
+        assert_eq!(u32::low_mask(31), 0x7FFF_FFFF);
+        assert_eq!(u32::low_mask(32), u32::MAX);
+
+        assert_eq!(u64::low_mask(0), 0);
+        assert_eq!(u64::low_mask(40), 0xFF_FFFF_FFFF);
+        assert_eq!(u64::low_mask(64), u64::MAX);
+
+        assert_eq!(u128::low_mask(0), 0);
+        assert_eq!(u128::low_mask(100), (1 << 100) - 1);
+        assert_eq!(u128::low_mask(128), u128::MAX);
+    }
+
+    #[test]
+    fn test_int_sign_extend() {
+        assert_eq!(0b101u8.sign_extend(3), 0xFD);
+        assert_eq!(0b101u16.sign_extend(3), 0xFFFD);
+        assert_eq!(0b101u32.sign_extend(3), 0xFFFF_FFFD);
+        assert_eq!(0b101u64.sign_extend(3), 0xFFFF_FFFF_FFFF_FFFD);
+        assert_eq!(0b101u128.sign_extend(3), u128::MAX - 2);
+
+        assert_eq!(0b011u32.sign_extend(3), 0b011);
+        assert_eq!(0xF5u8.sign_extend(3), 0xFD);
+        assert_eq!(0x8000u16.sign_extend(16), 0x8000);
+        assert_eq!(0xFFu8.sign_extend(0), 0);
+    }
+
+    #[test]
+    fn test_int_widen_narrow() {
+        assert_eq!(u8::MAX.widen_to_u128(), 0xFF);
+        assert_eq!(u16::MAX.widen_to_u128(), 0xFFFF);
+        assert_eq!(u32::MAX.widen_to_u128(), 0xFFFF_FFFF);
+        assert_eq!(u64::MAX.widen_to_u128(), 0xFFFF_FFFF_FFFF_FFFF);
+        assert_eq!(u128::MAX.widen_to_u128(), u128::MAX);
+
+        assert_eq!(u8::narrow_from_u128(0xFF), Some(u8::MAX));
+        assert_eq!(u8::narrow_from_u128(0x100), None);
+        assert_eq!(u16::narrow_from_u128(0xFFFF), Some(u16::MAX));
+        assert_eq!(u16::narrow_from_u128(0x1_0000), None);
+        assert_eq!(u32::narrow_from_u128(0xFFFF_FFFF), Some(u32::MAX));
+        assert_eq!(u32::narrow_from_u128(0x1_0000_0000), None);
+        assert_eq!(u64::narrow_from_u128(u64::MAX as u128), Some(u64::MAX));
+        assert_eq!(u64::narrow_from_u128(1 << 64), None);
+        assert_eq!(u128::narrow_from_u128(u128::MAX), Some(u128::MAX));
+
+        assert_eq!(u8::MAX.widen_to_u64(), 0xFF);
+        assert_eq!(u16::MAX.widen_to_u64(), 0xFFFF);
+        assert_eq!(u32::MAX.widen_to_u64(), 0xFFFF_FFFF);
+        assert_eq!(u64::MAX.widen_to_u64(), u64::MAX);
+        assert_eq!((u64::MAX as u128).widen_to_u64(), u64::MAX);
+
+        assert_eq!(u8::narrow_from_u64(0xFF), Some(u8::MAX));
+        assert_eq!(u8::narrow_from_u64(0x100), None);
+        assert_eq!(u16::narrow_from_u64(0xFFFF), Some(u16::MAX));
+        assert_eq!(u16::narrow_from_u64(0x1_0000), None);
+        assert_eq!(u32::narrow_from_u64(0xFFFF_FFFF), Some(u32::MAX));
+        assert_eq!(u32::narrow_from_u64(0x1_0000_0000), None);
+        assert_eq!(u64::narrow_from_u64(u64::MAX), Some(u64::MAX));
+        assert_eq!(u128::narrow_from_u64(u64::MAX), Some(u64::MAX as u128));
+    }
+
+    #[test]
+    #[should_panic(expected = "value does not fit into u64")]
+    fn test_int_widen_to_u64_overflow() {
+        (1u128 << 64).widen_to_u64();
+    }
+
+    #[allow(non_snake_case)]
+    mod CTRL {
+        use super::*;
+
+        pub const ENABLE: Field<u16, ReadWrite, ()> = Field::new(0b1, 0);
+        pub const MODE: Field<u16, ReadWrite, ()> = Field::new(0b11, 1);
+    }
+
+    #[test]
+    fn test_with_fields() {
+        let value = with_fields!(CTRL {
+            ENABLE: 1,
+            MODE: 0b10
+        });
+        assert_eq!(value.into_inner(), 0b101);
+
+        let single = with_fields!(self::CTRL { MODE: 0b01 });
+        assert_eq!(single.into_inner(), 0b010);
+    }
+
+    #[test]
+    fn test_custom_int() {
+        use core::ops::*;
+
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        #[repr(transparent)]
+        struct Sim(u16);
+
+        macro_rules! forward_ops {
+            ($($trait:ident::$method:ident),*) => {
+                $(
+                    impl $trait for Sim {
+                        type Output = Self;
+                        fn $method(self, rhs: Self) -> Self {
+                            Sim(self.0.$method(rhs.0))
+                        }
+                    }
+                )*
+            };
+        }
+
+        forward_ops!(BitAnd::bitand, BitOr::bitor, BitXor::bitxor);
+
+        impl Not for Sim {
+            type Output = Self;
+            fn not(self) -> Self {
+                Sim(!self.0)
+            }
+        }
+
+        impl Shl<usize> for Sim {
+            type Output = Self;
+            fn shl(self, rhs: usize) -> Self {
+                Sim(self.0 << rhs)
+            }
+        }
+
+        impl Shr<usize> for Sim {
+            type Output = Self;
+            fn shr(self, rhs: usize) -> Self {
+                Sim(self.0 >> rhs)
+            }
+        }
+
+        impl core::fmt::LowerHex for Sim {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::LowerHex::fmt(&self.0, f)
+            }
+        }
+
+        unsafe impl UnsafeIntImpl for Sim {}
+
+        impl Int for Sim {
+            const ZERO: Self = Sim(0);
+
+            fn count_ones(self) -> u32 {
+                self.0.count_ones()
+            }
+
+            fn trailing_zeros(self) -> u32 {
+                self.0.trailing_zeros()
+            }
+
+            fn low_mask(width: u32) -> Self {
+                Sim(u16::low_mask(width))
+            }
+
+            fn sign_extend(self, width: u32) -> Self {
+                Sim(self.0.sign_extend(width))
+            }
+
+            fn widen_to_u128(self) -> u128 {
+                self.0.widen_to_u128()
+            }
+
+            fn narrow_from_u128(value: u128) -> Option<Self> {
+                u16::narrow_from_u128(value).map(Sim)
+            }
+        }
+
+        let field = Field::<Sim, ReadWrite, ()>::new(Sim(0xF), 4);
+        assert_eq!(field.read(Sim(0x1234)), Sim(0x3));
+        assert!(field.is_set(Sim(0x0010)));
+        assert_eq!(field.popcount(Sim(0x00F0)), 4);
+
+        extern crate std;
+        let flag = Field::<Sim, ReadWrite, ()>::new(Sim(0b1), 8);
+        assert_eq!(
+            std::format!("{:?}", flag.bool_value(true)),
+            "FieldValue { mask: 0x100, value: 0x100 }"
+        );
+    }
 }
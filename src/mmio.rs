@@ -4,11 +4,17 @@
 //! structs to generate APIs around, whereas [`RegisterWindow`]s can be
 //! used for compile-time checked access to a register at runtime.
 
-use core::marker::PhantomData;
+use core::{
+    cell::Cell,
+    marker::PhantomData,
+    sync::atomic::{self, Ordering},
+};
 
 use crate::{
+    field::Field,
     perms::{self, Permission},
     register::*,
+    sealed::Sealed,
     Int,
 };
 
@@ -83,6 +89,502 @@ impl<'mmio, I: Int, P: Permission, R: RegisterMarker> RegisterWindow<'mmio, I, P
             __marker: PhantomData,
         }
     }
+
+    /// Constructs a window that is backed by a [`Cell`] in regular
+    /// memory rather than an actual MMIO register.
+    ///
+    /// This is intended for host-side simulation of devices, so that
+    /// unmodified driver code can be exercised without hardware.
+    pub fn from_cell(cell: &'mmio Cell<I>) -> Self {
+        // SAFETY: `Cell<I>` and `Register` are both repr(transparent)
+        // over `I` and `Cell` permits mutation through shared references.
+        unsafe { Self::new(cell.as_ptr().cast()) }
+    }
+
+    #[inline]
+    pub(crate) fn addr(&self) -> usize {
+        self.register as usize
+    }
+
+    /// Gets a pointer to the storage of the register, e.g. to hand its
+    /// address to a DMA engine.
+    ///
+    /// Accessing the register through this pointer bypasses the
+    /// permission checks of this window and must be done with volatile
+    /// operations.
+    #[inline]
+    pub fn as_ptr(&self) -> *const I {
+        self.register.cast()
+    }
+
+    /// Gets a mutable pointer to the storage of the register, e.g. to
+    /// hand its address to a DMA engine.
+    ///
+    /// Accessing the register through this pointer bypasses the
+    /// permission checks of this window and must be done with volatile
+    /// operations.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut I {
+        self.register.cast()
+    }
+}
+
+impl<'mmio, I: Int, P: Permission, R: RegisterMarker> RegisterWindow<'mmio, I, P, R> {
+    /// Erases the permission of this window into a [`RawWindow`].
+    ///
+    /// This allows storing windows of different permissions together,
+    /// e.g. in a runtime registry of registers for a debug shell.
+    pub fn raw_window(self) -> RawWindow<'mmio, I, R> {
+        RawWindow {
+            register: self.register.cast(),
+            readable: P::READABLE,
+            writable: P::WRITABLE,
+
+            __marker: PhantomData,
+        }
+    }
+}
+
+impl<'mmio, I: Int, R: RegisterMarker> RegisterWindow<'mmio, I, perms::ReadWrite, R> {
+    /// Reborrows the window as a read-only window to the same register.
+    ///
+    /// This narrows the permission at the type level only, e.g. to hand
+    /// a view to a subsystem that must not write the register.
+    #[inline]
+    pub fn as_read_only(&mut self) -> RegisterWindow<'_, I, perms::ReadOnly, R> {
+        // SAFETY: `Register` is repr(transparent) over `I` regardless of
+        // its permission, and the reborrow keeps `self` inaccessible.
+        unsafe { RegisterWindow::new(self.register.cast()) }
+    }
+
+    /// Reborrows the window as a write-only window to the same register.
+    ///
+    /// This narrows the permission at the type level only, e.g. to hand
+    /// a view to a subsystem that must not read the register.
+    #[inline]
+    pub fn as_write_only(&mut self) -> RegisterWindow<'_, I, perms::WriteOnly, R> {
+        // SAFETY: `Register` is repr(transparent) over `I` regardless of
+        // its permission, and the reborrow keeps `self` inaccessible.
+        unsafe { RegisterWindow::new(self.register.cast()) }
+    }
+
+    /// Splits the window into a read-only and a write-only window to the
+    /// same register which can be held at the same time.
+    ///
+    /// This suits duplex peripherals where one part of a driver only
+    /// observes a register while another part only writes to it.
+    ///
+    /// Note that the halves do not coordinate with each other: a write
+    /// through one half may change what the other half reads next, just
+    /// like it would from the hardware's point of view.
+    pub fn split_ro_wo(
+        &mut self,
+    ) -> (
+        RegisterWindow<'_, I, perms::ReadOnly, R>,
+        RegisterWindow<'_, I, perms::WriteOnly, R>,
+    ) {
+        // SAFETY: All accesses are volatile through raw pointers, so two
+        // windows to the same register do not violate aliasing rules.
+        unsafe {
+            (
+                RegisterWindow::new(self.register.cast()),
+                RegisterWindow::new(self.register.cast()),
+            )
+        }
+    }
+}
+
+impl<'mmio, I: Int, R: RegisterMarker> RegisterWindow<'mmio, I, perms::WriteOneToClear, R> {
+    /// Clears the given "write 1 to clear" `field` by writing ones to
+    /// all of its bits and zeroes everywhere else.
+    ///
+    /// This commits a direct write without reading the register. It is
+    /// only available for registers tagged
+    /// [`WriteOneToClear`][perms::WriteOneToClear] as a whole, i.e. whose
+    /// bits are all either "write 1 to clear" or read-only. Writing zeroes
+    /// to those leaves them untouched, so only `field` is cleared.
+    pub fn clear(&mut self, field: Field<I, perms::WriteOneToClear, R>) {
+        // SAFETY: All other bits of the register ignore zeroes, see above.
+        unsafe { self.register.set(field.select(!I::ZERO)) }
+    }
+}
+
+/// An access window to an array of identical [`Register`]s which are
+/// laid out at a fixed stride.
+///
+/// Individual registers are accessed through bounds-checked
+/// [`RegisterWindow`]s obtained with [`RegisterArrayWindow::index`].
+pub struct RegisterArrayWindow<'mmio, I: Int, P: Permission, R: RegisterMarker> {
+    base: *mut Register<I, P, R>,
+    stride: usize,
+    len: usize,
+
+    __marker: PhantomData<&'mmio ()>,
+}
+
+impl<'mmio, I: Int, P: Permission, R: RegisterMarker> RegisterArrayWindow<'mmio, I, P, R> {
+    // Not part of the public API. Used by generated code.
+    #[doc(hidden)]
+    pub unsafe fn new(base: *mut Register<I, P, R>, stride: usize, len: usize) -> Self {
+        Self {
+            base,
+            stride,
+            len,
+
+            __marker: PhantomData,
+        }
+    }
+
+    /// Constructs a window that is backed by a slice of [`Cell`]s in
+    /// regular memory rather than actual MMIO registers.
+    ///
+    /// This is the array counterpart to [`RegisterWindow::from_cell`].
+    pub fn from_cells(cells: &'mmio [Cell<I>]) -> Self {
+        // SAFETY: `Cell<I>` and `Register` are both repr(transparent)
+        // over `I` and `Cell` permits mutation through shared references.
+        unsafe {
+            Self::new(
+                cells.as_ptr() as *mut Register<I, P, R>,
+                core::mem::size_of::<I>(),
+                cells.len(),
+            )
+        }
+    }
+
+    /// Gets the number of registers in the array.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the array has no registers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets a window to the register at index `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is out of bounds for the array.
+    pub fn index(&mut self, n: usize) -> RegisterWindow<'_, I, P, R> {
+        let len = self.len;
+        self.get(n).unwrap_or_else(|| {
+            panic!(
+                "register index out of bounds: the len is {} but the index is {}",
+                len, n
+            )
+        })
+    }
+
+    /// Gets a window to the register at index `n`, or `None` if `n` is
+    /// out of bounds for the array.
+    pub fn get(&mut self, n: usize) -> Option<RegisterWindow<'_, I, P, R>> {
+        if n >= self.len {
+            return None;
+        }
+
+        // SAFETY: `n` is in bounds, so the register is part of the array.
+        unsafe {
+            let register = self.base.cast::<u8>().add(n * self.stride).cast();
+            Some(RegisterWindow::new(register))
+        }
+    }
+}
+
+/// A [`RegisterWindow`] whose permission was erased into a runtime value.
+///
+/// Obtained through [`RegisterWindow::raw_window`]. Typed windows can be
+/// recovered with fallible downcasts which check the stored permission.
+pub struct RawWindow<'mmio, I: Int, R: RegisterMarker> {
+    register: *mut I,
+    readable: bool,
+    writable: bool,
+
+    __marker: PhantomData<&'mmio R>,
+}
+
+impl<'mmio, I: Int, R: RegisterMarker> RawWindow<'mmio, I, R> {
+    /// Whether the register grants read access.
+    #[inline]
+    pub fn is_readable(&self) -> bool {
+        self.readable
+    }
+
+    /// Whether the register grants write access.
+    #[inline]
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    #[inline]
+    fn downcast<P: Permission>(&mut self, ok: bool) -> Option<RegisterWindow<'_, I, P, R>> {
+        // SAFETY: `Register` is repr(transparent) over `I` and the
+        // pointer stems from a valid `RegisterWindow`.
+        ok.then(|| unsafe { RegisterWindow::new(self.register.cast()) })
+    }
+
+    /// Borrows the register as a read-only [`RegisterWindow`].
+    ///
+    /// Returns `None` if the register is not readable.
+    pub fn as_readable(&mut self) -> Option<RegisterWindow<'_, I, perms::ReadOnly, R>> {
+        self.downcast(self.readable)
+    }
+
+    /// Borrows the register as a write-only [`RegisterWindow`].
+    ///
+    /// Returns `None` if the register is not writable.
+    pub fn as_writable(&mut self) -> Option<RegisterWindow<'_, I, perms::WriteOnly, R>> {
+        self.downcast(self.writable)
+    }
+
+    /// Borrows the register as a readable and writable [`RegisterWindow`].
+    ///
+    /// Returns `None` if the register is not both readable and writable.
+    pub fn as_read_write(&mut self) -> Option<RegisterWindow<'_, I, perms::ReadWrite, R>> {
+        self.downcast(self.readable && self.writable)
+    }
+}
+
+impl<'mmio, I: Int, R: RegisterMarker> DynRegister for RawWindow<'mmio, I, R> {
+    #[inline]
+    fn addr(&self) -> usize {
+        self.register as usize
+    }
+
+    #[inline]
+    fn width(&self) -> u32 {
+        (core::mem::size_of::<I>() * 8) as u32
+    }
+
+    unsafe fn read_raw(&mut self) -> Result<u128, DynAccessError> {
+        if !self.readable {
+            return Err(DynAccessError::Denied);
+        }
+
+        Ok(self.register.read_volatile().widen_to_u128())
+    }
+
+    unsafe fn write_raw(&mut self, value: u128) -> Result<(), DynAccessError> {
+        if !self.writable {
+            return Err(DynAccessError::Denied);
+        }
+
+        let value = I::narrow_from_u128(value).ok_or(DynAccessError::Overflow)?;
+        self.register.write_volatile(value);
+        Ok(())
+    }
+}
+
+/// Error indicating that a register is not aligned to the size of
+/// its storage type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Misaligned {
+    /// The offending address of the register.
+    pub addr: usize,
+}
+
+impl<'mmio, I: Int, P: Permission, R: RegisterMarker> RegisterWindow<'mmio, I, P, R> {
+    #[inline]
+    fn check_alignment(&self) -> Result<(), Misaligned> {
+        let addr = self.addr();
+        if crate::is_aligned(addr, core::mem::align_of::<I>()) {
+            Ok(())
+        } else {
+            Err(Misaligned { addr })
+        }
+    }
+}
+
+impl<'mmio, I, P, R> RegisterWindow<'mmio, I, P, R>
+where
+    I: Int,
+    P: perms::Readable,
+    R: RegisterMarker,
+{
+    /// Reads the raw value from the register after checking that it
+    /// is correctly aligned.
+    ///
+    /// Windows from generated code are always aligned, so this is only
+    /// useful for windows to registers at runtime-computed addresses.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterRead::get`].
+    pub unsafe fn try_get(&mut self) -> Result<I, Misaligned> {
+        self.check_alignment()?;
+        Ok(self.register.get())
+    }
+
+    /// Reads the raw value from the register and issues an
+    /// [`atomic::fence`] with the given `order` after the read.
+    ///
+    /// This keeps memory accesses following the read from being
+    /// reordered before it, as observed by other CPUs. Plain reads
+    /// through [`RegisterRead::get`] do not fence.
+    ///
+    /// The fence is the one of the Rust memory model and only orders
+    /// accesses between CPUs. It is **not** a device or DMA barrier:
+    ///
+    /// - On AArch64 and ARM, it lowers to `dmb ish` or `dmb ishld`,
+    ///   which cover the inner shareable domain only. Device memory
+    ///   accesses are not ordered against DMA masters in the outer
+    ///   shareable domain, which needs `dmb osh`, `dmb sy` or `dsb`.
+    /// - On RISC-V, it lowers to `fence` without the `i` and `o` bits,
+    ///   so device I/O is not ordered either.
+    /// - On x86, which is strongly ordered, it mostly constrains the
+    ///   compiler.
+    ///
+    /// Use the architecture's own barrier instructions where a
+    /// peripheral or DMA engine must observe the ordering.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is not [`Ordering::Acquire`] or
+    /// [`Ordering::SeqCst`]. Use [`RegisterRead::get`] for reads without
+    /// a fence.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterRead::get`].
+    pub unsafe fn get_ordered(&mut self, order: Ordering) -> I {
+        match order {
+            Ordering::Acquire | Ordering::SeqCst => {}
+            _ => panic!("ordered register reads must be Acquire or SeqCst"),
+        }
+
+        let value = self.register.get();
+        atomic::fence(order);
+
+        value
+    }
+
+    /// Fills `buf` by repeatedly reading the raw register value, e.g.
+    /// to drain a data FIFO.
+    ///
+    /// Returns the number of values read, which is always the length
+    /// of `buf`. See [`RegisterWindow::read_into_while`] for draining
+    /// only as much data as is available.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterRead::get`].
+    pub unsafe fn read_into(&mut self, buf: &mut [I]) -> usize {
+        for slot in buf.iter_mut() {
+            *slot = self.register.get();
+        }
+
+        buf.len()
+    }
+
+    /// Fills `buf` by repeatedly reading the raw register value for as
+    /// long as `available` is set in the `status` register.
+    ///
+    /// `status` is checked before every read of this register, so a
+    /// FIFO is drained until it reports being empty or `buf` is full.
+    ///
+    /// Returns the number of values read into `buf`.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterRead::get`].
+    pub unsafe fn read_into_while<S, Q>(
+        &mut self,
+        buf: &mut [I],
+        status: &mut S,
+        available: Field<S::Register, Q, S::Marker>,
+    ) -> usize
+    where
+        S: RegisterRead,
+        Q: perms::Readable,
+    {
+        let mut count = 0;
+        for slot in buf.iter_mut() {
+            if !status.is_set(available) {
+                break;
+            }
+
+            *slot = self.register.get();
+            count += 1;
+        }
+
+        count
+    }
+}
+
+impl<'mmio, I, P, R> RegisterWindow<'mmio, I, P, R>
+where
+    I: Int,
+    P: perms::Writable,
+    R: RegisterMarker,
+{
+    /// Writes the given raw `value` to the register after checking that
+    /// it is correctly aligned.
+    ///
+    /// Windows from generated code are always aligned, so this is only
+    /// useful for windows to registers at runtime-computed addresses.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterWrite::set`].
+    pub unsafe fn try_set(&mut self, value: I) -> Result<(), Misaligned> {
+        self.check_alignment()?;
+        self.register.set(value);
+        Ok(())
+    }
+
+    /// Issues an [`atomic::fence`] with the given `order` and writes
+    /// the raw `value` to the register afterwards.
+    ///
+    /// This keeps memory accesses preceding the write from being
+    /// reordered after it, as observed by other CPUs. Plain writes
+    /// through [`RegisterWrite::set`] do not fence.
+    ///
+    /// See [`RegisterWindow::get_ordered`] for why this is not a device
+    /// or DMA barrier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is not [`Ordering::Release`] or
+    /// [`Ordering::SeqCst`]. Use [`RegisterWrite::set`] for writes
+    /// without a fence.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterWrite::set`].
+    pub unsafe fn set_ordered(&mut self, value: I, order: Ordering) {
+        match order {
+            Ordering::Release | Ordering::SeqCst => {}
+            _ => panic!("ordered register writes must be Release or SeqCst"),
+        }
+
+        atomic::fence(order);
+        self.register.set(value);
+    }
+}
+
+impl<'mmio, I, P, R> RegisterWindow<'mmio, I, P, R>
+where
+    I: Int,
+    P: perms::Readable + perms::Writable,
+    R: RegisterMarker,
+{
+    /// Replaces the bits selected by `mask` in the register with the
+    /// corresponding bits of `value`, leaving everything else unchanged.
+    ///
+    /// This performs a read-modify-write without the need to construct
+    /// a [`FieldValue`][crate::field::FieldValue].
+    ///
+    /// # Safety
+    ///
+    /// Access permissions to the individual bit fields of the register
+    /// are not enforced. The user needs to make sure that `mask` does not
+    /// cover read-only or reserved bits.
+    pub unsafe fn modify_masked(&mut self, value: I, mask: I) {
+        let current = self.register.get();
+        self.register.set((current & !mask) | (value & mask));
+    }
 }
 
 // SAFETY: Register has `Readable` permission.
@@ -117,26 +619,713 @@ where
     }
 }
 
+/// Primitive integer types which can be accessed atomically.
+///
+/// This trait is sealed and only implemented for the integer widths the
+/// target provides atomic types for.
+pub trait AtomicInt: Int + atomic_sealed::Sealed {}
+
+mod atomic_sealed {
+    use core::sync::atomic::{self, Ordering};
+
+    pub trait Sealed: Sized {
+        type Atomic;
+
+        fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+        fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+    }
+
+    macro_rules! impl_atomic_int {
+        ($($(#[$attr:meta])* $ty:ty => $atomic:ty),* $(,)?) => {
+            $(
+                $(#[$attr])*
+                impl Sealed for $ty {
+                    type Atomic = $atomic;
+
+                    #[inline]
+                    fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                        atomic.load(order)
+                    }
+
+                    #[inline]
+                    fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                        atomic.store(value, order)
+                    }
+                }
+
+                $(#[$attr])*
+                impl super::AtomicInt for $ty {}
+            )*
+        };
+    }
+
+    impl_atomic_int! {
+        #[cfg(target_has_atomic = "8")]
+        u8 => atomic::AtomicU8,
+        #[cfg(target_has_atomic = "16")]
+        u16 => atomic::AtomicU16,
+        #[cfg(target_has_atomic = "32")]
+        u32 => atomic::AtomicU32,
+        #[cfg(target_has_atomic = "64")]
+        u64 => atomic::AtomicU64,
+    }
+}
+
+/// Representation of a Memory-Mapped I/O (MMIO) register which is
+/// accessed with atomic loads and stores.
+///
+/// This is the atomic counterpart to [`Register`] for registers that
+/// are shared between cores. Windows to it are [`AtomicRegisterWindow`]s.
+#[repr(transparent)]
+pub struct AtomicRegister<I: AtomicInt, P: Permission, R: RegisterMarker> {
+    value: <I as atomic_sealed::Sealed>::Atomic,
+
+    __perm: PhantomData<P>,
+    __reg: PhantomData<R>,
+}
+
+/// An access window to an [`AtomicRegister`].
+///
+/// Unlike [`RegisterWindow`], this window may be shared between threads
+/// since every access is a single atomic load or store.
+///
+/// Note that a read-modify-write through this window still consists of
+/// two separate accesses and is therefore not atomic as a whole.
+pub struct AtomicRegisterWindow<'mmio, I: AtomicInt, P: Permission, R: RegisterMarker> {
+    register: *const AtomicRegister<I, P, R>,
+    ordering: Ordering,
+
+    __marker: PhantomData<&'mmio ()>,
+}
+
+// SAFETY: All accesses to the register are atomic.
+unsafe impl<I: AtomicInt, P: Permission, R: RegisterMarker> Send
+    for AtomicRegisterWindow<'_, I, P, R>
+{
+}
+unsafe impl<I: AtomicInt, P: Permission, R: RegisterMarker> Sync
+    for AtomicRegisterWindow<'_, I, P, R>
+{
+}
+
+impl<'mmio, I: AtomicInt, P: Permission, R: RegisterMarker> AtomicRegisterWindow<'mmio, I, P, R> {
+    // Not part of the public API. Used by generated code.
+    #[doc(hidden)]
+    pub unsafe fn new(register: *const AtomicRegister<I, P, R>, ordering: Ordering) -> Self {
+        Self {
+            register,
+            ordering,
+
+            __marker: PhantomData,
+        }
+    }
+
+    /// Constructs a window that is backed by an atomic in regular memory
+    /// rather than an actual MMIO register.
+    ///
+    /// This is intended for host-side simulation of devices, much like
+    /// [`RegisterWindow::from_cell`].
+    ///
+    /// See [`AtomicRegisterWindow::ordering`] for how `ordering` applies.
+    pub fn from_atomic(
+        atomic: &'mmio <I as atomic_sealed::Sealed>::Atomic,
+        ordering: Ordering,
+    ) -> Self {
+        // SAFETY: `AtomicRegister` is repr(transparent) over the atomic.
+        unsafe {
+            Self::new(
+                (atomic as *const <I as atomic_sealed::Sealed>::Atomic).cast(),
+                ordering,
+            )
+        }
+    }
+
+    /// Gets the memory ordering of accesses through this window.
+    ///
+    /// Reads use the acquire part of the ordering and writes use the
+    /// release part, e.g. [`Ordering::AcqRel`] makes reads acquire and
+    /// writes release. [`Ordering::Acquire`] only applies to reads and
+    /// [`Ordering::Release`] only applies to writes, the respective
+    /// other access is relaxed.
+    #[inline]
+    pub fn ordering(&self) -> Ordering {
+        self.ordering
+    }
+
+    #[inline]
+    fn atomic(&self) -> &<I as atomic_sealed::Sealed>::Atomic {
+        // SAFETY: The window was constructed from a valid pointer.
+        unsafe { &(*self.register).value }
+    }
+}
+
+// SAFETY: Register has `Readable` permission.
+unsafe impl<'mmio, I, P, R> RegisterRead for AtomicRegisterWindow<'mmio, I, P, R>
+where
+    I: AtomicInt,
+    P: perms::Readable,
+    R: RegisterMarker,
+{
+    type Register = I;
+    type Marker = R;
+
+    #[inline]
+    unsafe fn get(&mut self) -> Self::Register {
+        let order = match self.ordering {
+            Ordering::Release => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Acquire,
+            order => order,
+        };
+
+        I::load(self.atomic(), order)
+    }
+}
+
+// SAFETY: Register has `Writable` permission.
+unsafe impl<'mmio, I, P, R> RegisterWrite for AtomicRegisterWindow<'mmio, I, P, R>
+where
+    I: AtomicInt,
+    P: perms::Writable,
+    R: RegisterMarker,
+{
+    type Register = I;
+    type Marker = R;
+
+    #[inline]
+    unsafe fn set(&mut self, value: Self::Register) {
+        let order = match self.ordering {
+            Ordering::Acquire => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Release,
+            order => order,
+        };
+
+        I::store(self.atomic(), value, order)
+    }
+}
+
+/// The order in which a [`SplitRegisterWindow`] accesses the two halves
+/// of a 64-bit register.
+///
+/// This trait is sealed and implemented by [`LowFirst`] and [`HighFirst`].
+pub trait SplitOrder: Sealed {
+    /// Whether the low half is accessed before the high half.
+    const LOW_FIRST: bool;
+}
+
+/// Accesses the low 32 bits of a split register before the high 32 bits.
+pub struct LowFirst;
+impl Sealed for LowFirst {}
+impl SplitOrder for LowFirst {
+    const LOW_FIRST: bool = true;
+}
+
+/// Accesses the high 32 bits of a split register before the low 32 bits.
+pub struct HighFirst;
+impl Sealed for HighFirst {}
+impl SplitOrder for HighFirst {
+    const LOW_FIRST: bool = false;
+}
+
+// The indices of the low and high half of a `u64` in memory.
+#[cfg(target_endian = "little")]
+const SPLIT_HALVES: [usize; 2] = [0, 1];
+#[cfg(target_endian = "big")]
+const SPLIT_HALVES: [usize; 2] = [1, 0];
+
+/// Reads a `u64` through `read`, which reads the `u32` at the given
+/// index, in the halves order `O`.
+#[inline]
+fn read_split<O: SplitOrder>(mut read: impl FnMut(usize) -> u32) -> u64 {
+    let [low, high] = SPLIT_HALVES;
+    let (low, high) = if O::LOW_FIRST {
+        let low = read(low);
+        (low, read(high))
+    } else {
+        let high = read(high);
+        (read(low), high)
+    };
+
+    (high as u64) << 32 | low as u64
+}
+
+/// Writes a `u64` through `write`, which writes the `u32` at the given
+/// index, in the halves order `O`.
+#[inline]
+fn write_split<O: SplitOrder>(value: u64, mut write: impl FnMut(usize, u32)) {
+    let [low, high] = SPLIT_HALVES;
+    if O::LOW_FIRST {
+        write(low, value as u32);
+        write(high, (value >> 32) as u32);
+    } else {
+        write(high, (value >> 32) as u32);
+        write(low, value as u32);
+    }
+}
+
+/// An access window to a 64-bit [`Register`] on a 32-bit bus.
+///
+/// Every access to the register is performed as two aligned 32-bit
+/// volatile accesses in the order given by `O`, as required by many
+/// peripherals on 32-bit targets.
+///
+/// Note that the two halves are not accessed atomically: a value which
+/// changes in between, such as a free-running counter, may be torn.
+pub struct SplitRegisterWindow<'mmio, O: SplitOrder, P: Permission, R: RegisterMarker> {
+    register: *mut Register<u64, P, R>,
+
+    __order: PhantomData<O>,
+    __marker: PhantomData<&'mmio ()>,
+}
+
+impl<'mmio, O: SplitOrder, P: Permission, R: RegisterMarker> SplitRegisterWindow<'mmio, O, P, R> {
+    // Not part of the public API. Used by generated code.
+    #[doc(hidden)]
+    pub unsafe fn new(register: *mut Register<u64, P, R>) -> Self {
+        Self {
+            register,
+
+            __order: PhantomData,
+            __marker: PhantomData,
+        }
+    }
+
+    /// Constructs a window that is backed by a [`Cell`] in regular
+    /// memory rather than an actual MMIO register.
+    ///
+    /// This is the split counterpart to [`RegisterWindow::from_cell`].
+    pub fn from_cell(cell: &'mmio Cell<u64>) -> Self {
+        // SAFETY: `Cell<u64>` and `Register` are both repr(transparent)
+        // over `u64` and `Cell` permits mutation through shared references.
+        unsafe { Self::new(cell.as_ptr().cast()) }
+    }
+
+    #[inline]
+    fn halves(&self) -> *mut u32 {
+        self.register.cast()
+    }
+}
+
+// SAFETY: Register has `Readable` permission.
+unsafe impl<'mmio, O, P, R> RegisterRead for SplitRegisterWindow<'mmio, O, P, R>
+where
+    O: SplitOrder,
+    P: perms::Readable,
+    R: RegisterMarker,
+{
+    type Register = u64;
+    type Marker = R;
+
+    #[inline]
+    unsafe fn get(&mut self) -> Self::Register {
+        let halves = self.halves();
+        read_split::<O>(|index| halves.add(index).read_volatile())
+    }
+}
+
+// SAFETY: Register has `Writable` permission.
+unsafe impl<'mmio, O, P, R> RegisterWrite for SplitRegisterWindow<'mmio, O, P, R>
+where
+    O: SplitOrder,
+    P: perms::Writable,
+    R: RegisterMarker,
+{
+    type Register = u64;
+    type Marker = R;
+
+    #[inline]
+    unsafe fn set(&mut self, value: Self::Register) {
+        let halves = self.halves();
+        write_split::<O>(value, |index, half| halves.add(index).write_volatile(half))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use static_assertions::assert_not_impl_all;
+    use static_assertions::{assert_impl_all, assert_not_impl_all};
 
     use super::*;
-    use crate::perms::ReadWrite;
+    use crate::perms::{ReadOnly, ReadWrite, WriteOnly};
 
     #[test]
     fn test_register_no_sync_send() {
-        assert_not_impl_all!(RegisterWindow<u8, ReadWrite>: Sync, Send);
-        assert_not_impl_all!(RegisterWindow<u16, ReadWrite>: Sync, Send);
-        assert_not_impl_all!(RegisterWindow<u32, ReadWrite>: Sync, Send);
-        assert_not_impl_all!(RegisterWindow<u64, ReadWrite>: Sync, Send);
+        assert_not_impl_all!(RegisterWindow<u8, ReadWrite, ()>: Sync, Send);
+        assert_not_impl_all!(RegisterWindow<u16, ReadWrite, ()>: Sync, Send);
+        assert_not_impl_all!(RegisterWindow<u32, ReadWrite, ()>: Sync, Send);
+        assert_not_impl_all!(RegisterWindow<u64, ReadWrite, ()>: Sync, Send);
+        assert_not_impl_all!(RegisterWindow<u128, ReadWrite, ()>: Sync, Send);
     }
 
     #[test]
     fn test_register_no_clone_copy() {
-        assert_not_impl_all!(RegisterWindow<u8, ReadWrite>: Clone, Copy);
-        assert_not_impl_all!(RegisterWindow<u16, ReadWrite>: Clone, Copy);
-        assert_not_impl_all!(RegisterWindow<u32, ReadWrite>: Clone, Copy);
-        assert_not_impl_all!(RegisterWindow<u64, ReadWrite>: Clone, Copy);
+        assert_not_impl_all!(RegisterWindow<u8, ReadWrite, ()>: Clone, Copy);
+        assert_not_impl_all!(RegisterWindow<u16, ReadWrite, ()>: Clone, Copy);
+        assert_not_impl_all!(RegisterWindow<u32, ReadWrite, ()>: Clone, Copy);
+        assert_not_impl_all!(RegisterWindow<u64, ReadWrite, ()>: Clone, Copy);
+        assert_not_impl_all!(RegisterWindow<u128, ReadWrite, ()>: Clone, Copy);
+    }
+
+    #[test]
+    fn test_register_from_cell() {
+        const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+
+        fn enable<W>(reg: &mut W)
+        where
+            W: RegisterRead<Register = u32, Marker = ()>
+                + RegisterWrite<Register = u32, Marker = ()>,
+        {
+            if !reg.is_set(ENABLE) {
+                reg.write(ENABLE.make_value(1));
+            }
+        }
+
+        let cell = Cell::new(0xFF00);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+        enable(&mut reg);
+
+        assert!(reg.is_set(ENABLE));
+        assert_eq!(cell.get(), 0x1);
+    }
+
+    #[test]
+    fn test_register_drain_fifo() {
+        // Status register that reports data for the next `pending` reads.
+        struct Status {
+            pending: u32,
+        }
+
+        unsafe impl RegisterRead for Status {
+            type Register = u32;
+            type Marker = ();
+
+            unsafe fn get(&mut self) -> u32 {
+                let value = (self.pending != 0) as u32;
+                self.pending = self.pending.saturating_sub(1);
+                value
+            }
+        }
+
+        const AVAILABLE: Field<u32, ReadOnly, ()> = Field::new(0b1, 0);
+
+        let cell = Cell::new(0xAB);
+        let mut data = RegisterWindow::<u8, ReadOnly, ()>::from_cell(&cell);
+
+        let mut buf = [0; 8];
+        let mut status = Status { pending: 3 };
+        let count = unsafe { data.read_into_while(&mut buf, &mut status, AVAILABLE) };
+        assert_eq!(count, 3);
+        assert_eq!(buf, [0xAB, 0xAB, 0xAB, 0, 0, 0, 0, 0]);
+
+        let mut buf = [0; 2];
+        let mut status = Status { pending: 3 };
+        let count = unsafe { data.read_into_while(&mut buf, &mut status, AVAILABLE) };
+        assert_eq!(count, 2);
+
+        let mut buf = [0; 4];
+        assert_eq!(unsafe { data.read_into(&mut buf) }, 4);
+        assert_eq!(buf, [0xAB; 4]);
+    }
+
+    #[test]
+    fn test_register_try_access() {
+        #[repr(align(4))]
+        struct Aligned([u8; 8]);
+
+        let mut mem = Aligned([0; 8]);
+        let base = mem.0.as_mut_ptr();
+
+        let mut aligned = unsafe { RegisterWindow::<u32, ReadWrite, ()>::new(base.cast()) };
+        assert_eq!(unsafe { aligned.try_set(0x1234_5678) }, Ok(()));
+        assert_eq!(unsafe { aligned.try_get() }, Ok(0x1234_5678));
+
+        let addr = base as usize + 1;
+        let mut misaligned =
+            unsafe { RegisterWindow::<u32, ReadWrite, ()>::new(base.add(1).cast()) };
+        assert_eq!(unsafe { misaligned.try_get() }, Err(Misaligned { addr }));
+        assert_eq!(unsafe { misaligned.try_set(0) }, Err(Misaligned { addr }));
+    }
+
+    #[test]
+    fn test_register_raw_window() {
+        const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+
+        let ctrl = Cell::new(0);
+        let data = Cell::new(0);
+        let mut windows = [
+            RegisterWindow::<u32, ReadWrite, ()>::from_cell(&ctrl).raw_window(),
+            RegisterWindow::<u32, WriteOnly, ()>::from_cell(&data).raw_window(),
+        ];
+
+        let ctrl_window = &mut windows[0];
+        assert!(ctrl_window.is_readable() && ctrl_window.is_writable());
+        ctrl_window
+            .as_read_write()
+            .unwrap()
+            .write(ENABLE.make_value(1));
+        assert!(ctrl_window.as_readable().unwrap().is_set(ENABLE));
+
+        let data_window = &mut windows[1];
+        assert!(!data_window.is_readable() && data_window.is_writable());
+        assert!(data_window.as_readable().is_none());
+        assert!(data_window.as_read_write().is_none());
+        unsafe { data_window.as_writable().unwrap().set(0xAB) };
+        assert_eq!(data.get(), 0xAB);
+    }
+
+    #[test]
+    fn test_register_modify_masked() {
+        let cell = Cell::new(0xAAAA_AAAA);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        unsafe { reg.modify_masked(0x1234_5678, 0x0000_FF00) };
+        assert_eq!(cell.get(), 0xAAAA_56AA);
+
+        unsafe { reg.modify_masked(0xFFFF_FFFF, 0) };
+        assert_eq!(cell.get(), 0xAAAA_56AA);
+    }
+
+    #[test]
+    fn test_register_split_ro_wo() {
+        const START: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+
+        let cell = Cell::new(0);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+        let (mut status, mut control) = reg.split_ro_wo();
+
+        assert!(!status.is_set(START));
+        control.write(START.make_value(1));
+        assert!(status.is_set(START));
+
+        assert_not_impl_all!(RegisterWindow<u32, ReadOnly, ()>: RegisterWrite);
+        assert_not_impl_all!(RegisterWindow<u32, WriteOnly, ()>: RegisterRead);
+    }
+
+    #[test]
+    fn test_register_u128() {
+        const KEY_HIGH: Field<u128, ReadWrite, ()> = Field::new(0xFFFF_FFFF_FFFF_FFFF, 64);
+
+        let cell = Cell::new(0x1234);
+        let mut reg = RegisterWindow::<u128, ReadWrite, ()>::from_cell(&cell);
+
+        reg.modify(KEY_HIGH.make_value(0xDEAD_BEEF_CAFE_F00D));
+        assert_eq!(reg.read(KEY_HIGH), 0xDEAD_BEEF_CAFE_F00D);
+        assert_eq!(cell.get(), 0xDEAD_BEEF_CAFE_F00D_0000_0000_0000_1234);
+    }
+
+    #[test]
+    fn test_dyn_register() {
+        extern crate std;
+        use std::boxed::Box;
+
+        let status = Cell::new(0x81u8);
+        let data = Cell::new(0u32);
+
+        let mut regs: [Box<dyn DynRegister>; 2] = [
+            Box::new(RegisterWindow::<u8, ReadOnly, ()>::from_cell(&status).raw_window()),
+            Box::new(RegisterWindow::<u32, ReadWrite, ()>::from_cell(&data).raw_window()),
+        ];
+
+        assert_eq!(regs[0].addr(), status.as_ptr() as usize);
+        assert_eq!(regs[0].width(), 8);
+        assert_eq!(regs[1].width(), 32);
+
+        unsafe {
+            assert_eq!(regs[0].read_raw(), Ok(0x81));
+            assert_eq!(regs[0].write_raw(0), Err(DynAccessError::Denied));
+
+            assert_eq!(regs[1].write_raw(0xDEAD_BEEF), Ok(()));
+            assert_eq!(regs[1].read_raw(), Ok(0xDEAD_BEEF));
+            assert_eq!(
+                regs[1].write_raw(0x1_0000_0000),
+                Err(DynAccessError::Overflow)
+            );
+        }
+        assert_eq!(data.get(), 0xDEAD_BEEF);
+    }
+
+    #[test]
+    fn test_register_as_ptr() {
+        let cell = Cell::new(0x1234u16);
+        let mut reg = RegisterWindow::<u16, ReadOnly, ()>::from_cell(&cell);
+
+        assert_eq!(reg.as_ptr(), cell.as_ptr() as *const u16);
+        assert_eq!(reg.as_mut_ptr(), cell.as_ptr());
+
+        // SAFETY: The pointer refers to `cell`, which is still alive.
+        unsafe { reg.as_mut_ptr().write_volatile(0x5678) };
+        assert_eq!(cell.get(), 0x5678);
+    }
+
+    #[test]
+    fn test_atomic_register_sync() {
+        assert_impl_all!(AtomicRegisterWindow<u8, ReadWrite, ()>: Sync, Send);
+        assert_impl_all!(AtomicRegisterWindow<u32, ReadWrite, ()>: Sync, Send);
+        assert_not_impl_all!(AtomicRegisterWindow<u8, ReadWrite, ()>: Clone, Copy);
+        assert_not_impl_all!(AtomicRegisterWindow<u32, ReadWrite, ()>: Clone, Copy);
+        assert_not_impl_all!(AtomicRegisterWindow<u32, ReadOnly, ()>: RegisterWrite);
+        assert_not_impl_all!(AtomicRegisterWindow<u32, WriteOnly, ()>: RegisterRead);
+    }
+
+    #[test]
+    fn test_atomic_register_access() {
+        const MODE: Field<u32, ReadWrite, ()> = Field::new(0b11, 4);
+
+        let atomic = core::sync::atomic::AtomicU32::new(0x0F);
+        let mut reg =
+            AtomicRegisterWindow::<u32, ReadWrite, ()>::from_atomic(&atomic, Ordering::AcqRel);
+        assert_eq!(reg.ordering(), Ordering::AcqRel);
+
+        reg.modify(MODE.make_value(0b10));
+        assert_eq!(reg.read(MODE), 0b10);
+        assert_eq!(atomic.load(Ordering::Relaxed), 0x2F);
+    }
+
+    #[test]
+    fn test_register_array() {
+        const LEVEL: Field<u32, ReadWrite, ()> = Field::new(0xFF, 0);
+
+        let cells = [Cell::new(0), Cell::new(0), Cell::new(0), Cell::new(0)];
+        let mut regs = RegisterArrayWindow::<u32, ReadWrite, ()>::from_cells(&cells);
+        assert_eq!(regs.len(), 4);
+
+        for n in 0..regs.len() {
+            regs.index(n).write(LEVEL.make_value(n as u32 * 0x10));
+        }
+        assert_eq!(regs.index(2).read(LEVEL), 0x20);
+        assert_eq!(cells[3].get(), 0x30);
+
+        assert!(regs.get(3).is_some());
+        assert!(regs.get(4).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "register index out of bounds")]
+    fn test_register_array_out_of_bounds() {
+        let cells = [Cell::new(0u8), Cell::new(0)];
+        let mut regs = RegisterArrayWindow::<u8, ReadOnly, ()>::from_cells(&cells);
+        regs.index(2);
+    }
+
+    #[test]
+    fn test_register_ordered_access() {
+        let cell = Cell::new(0u32);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        for &order in &[Ordering::Release, Ordering::SeqCst] {
+            unsafe { reg.set_ordered(0xCAFE_F00D, order) };
+            assert_eq!(cell.get(), 0xCAFE_F00D);
+            cell.set(0);
+        }
+
+        cell.set(0x1234_5678);
+        for &order in &[Ordering::Acquire, Ordering::SeqCst] {
+            assert_eq!(unsafe { reg.get_ordered(order) }, 0x1234_5678);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "ordered register reads must be Acquire or SeqCst")]
+    fn test_register_get_ordered_release() {
+        let cell = Cell::new(0u32);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        unsafe { reg.get_ordered(Ordering::Release) };
+    }
+
+    #[test]
+    #[should_panic(expected = "ordered register writes must be Release or SeqCst")]
+    fn test_register_set_ordered_relaxed() {
+        let cell = Cell::new(0u32);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        unsafe { reg.set_ordered(0, Ordering::Relaxed) };
+    }
+
+    #[test]
+    fn test_split_register_order() {
+        // A byte-addressable mock which records the order of accesses.
+        let [low, high] = SPLIT_HALVES;
+        let mut memory = [0u32; 2];
+        memory[low] = 0x89AB_CDEF;
+        memory[high] = 0x0123_4567;
+
+        let mut accesses = [usize::MAX; 2];
+        let mut count = 0;
+        let value = read_split::<LowFirst>(|index| {
+            accesses[count] = index;
+            count += 1;
+            memory[index]
+        });
+        assert_eq!(accesses, [low, high]);
+        assert_eq!(value, 0x0123_4567_89AB_CDEF);
+
+        let mut written = [(usize::MAX, 0); 2];
+        let mut count = 0;
+        write_split::<HighFirst>(0x0123_4567_89AB_CDEF, |index, half| {
+            written[count] = (index, half);
+            count += 1;
+        });
+        assert_eq!(written, [(high, 0x0123_4567), (low, 0x89AB_CDEF)]);
+    }
+
+    #[test]
+    fn test_split_register_access() {
+        const HIGH: Field<u64, ReadWrite, ()> = Field::new(0xFFFF_FFFF, 32);
+
+        let cell = Cell::new(0x0000_0000_DEAD_BEEFu64);
+        let mut reg = SplitRegisterWindow::<HighFirst, ReadWrite, ()>::from_cell(&cell);
+
+        reg.modify(HIGH.make_value(0xCAFE_F00D));
+        assert_eq!(cell.get(), 0xCAFE_F00D_DEAD_BEEF);
+        assert_eq!(reg.read(HIGH), 0xCAFE_F00D);
+
+        let mut reg = SplitRegisterWindow::<LowFirst, ReadWrite, ()>::from_cell(&cell);
+        reg.write(HIGH.make_value(1));
+        assert_eq!(cell.get(), 0x0000_0001_0000_0000);
+    }
+
+    #[test]
+    fn test_register_narrow_permission() {
+        const LOW: Field<u32, ReadWrite, ()> = Field::new(0xFF, 0);
+
+        assert_not_impl_all!(RegisterWindow<u32, ReadOnly, ()>: RegisterWrite);
+        assert_not_impl_all!(RegisterWindow<u32, WriteOnly, ()>: RegisterRead);
+
+        let cell = Cell::new(0x12);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        let mut ro: RegisterWindow<u32, ReadOnly, ()> = reg.as_read_only();
+        assert_eq!(ro.read(LOW), 0x12);
+
+        let mut wo: RegisterWindow<u32, WriteOnly, ()> = reg.as_write_only();
+        wo.write(LOW.make_value(0x34));
+
+        assert_eq!(reg.read(LOW), 0x34);
+    }
+
+    #[test]
+    fn test_write_one_to_clear() {
+        use crate::perms::WriteOneToClear;
+
+        const RX: Field<u32, WriteOneToClear, ()> = Field::new(0b1, 0);
+        const TX: Field<u32, WriteOneToClear, ()> = Field::new(0b1, 1);
+        const ERR: Field<u32, WriteOneToClear, ()> = Field::new(0b11, 4);
+
+        // No read-modify-write may acknowledge pending flags by accident.
+        assert_not_impl_all!(RegisterWindow<u32, WriteOneToClear, ()>: RegisterWrite, RegisterReadWrite);
+        assert_not_impl_all!(WriteOneToClear: perms::Writable);
+
+        let cell = Cell::new(0b11_0011);
+        let mut reg = RegisterWindow::<u32, WriteOneToClear, ()>::from_cell(&cell);
+        assert!(reg.is_set(TX));
+
+        reg.clear(TX);
+        assert_eq!(cell.get(), 0b00_0010);
+
+        reg.clear(ERR);
+        assert_eq!(cell.get(), 0b11_0000);
+
+        reg.clear(RX);
+        assert_eq!(cell.get(), 0b00_0001);
     }
 }
@@ -11,7 +11,13 @@ use crate::sealed::Sealed;
 /// [`Field`][crate::field::Field] and [`FieldValue`][crate::field::FieldValue]
 /// are made covariant over particular [`Permission`]s to prevent misuse of
 /// APIs when applied incorrectly.
-pub trait Permission: Sealed {}
+pub trait Permission: Sealed {
+    /// Whether the permission grants read access.
+    const READABLE: bool;
+
+    /// Whether the permission grants write access.
+    const WRITABLE: bool;
+}
 
 /// Specialized trait to mark permissions which grant read access to a
 /// [`Field`][crate::field::Field].
@@ -24,18 +30,76 @@ pub trait Writable: Permission {}
 /// Permission marker to tag read-only register fields.
 pub struct ReadOnly;
 impl Sealed for ReadOnly {}
-impl Permission for ReadOnly {}
+impl Permission for ReadOnly {
+    const READABLE: bool = true;
+    const WRITABLE: bool = false;
+}
 impl Readable for ReadOnly {}
 
 /// Permission marker to tag write-only register fields.
 pub struct WriteOnly;
 impl Sealed for WriteOnly {}
-impl Permission for WriteOnly {}
+impl Permission for WriteOnly {
+    const READABLE: bool = false;
+    const WRITABLE: bool = true;
+}
 impl Writable for WriteOnly {}
 
 /// Permission marker to tag both readable and writable register fields.
 pub struct ReadWrite;
 impl Sealed for ReadWrite {}
-impl Permission for ReadWrite {}
+impl Permission for ReadWrite {
+    const READABLE: bool = true;
+    const WRITABLE: bool = true;
+}
 impl Readable for ReadWrite {}
 impl Writable for ReadWrite {}
+
+/// Permission marker to tag "read to clear" register fields, such as
+/// FIFO data or sticky status flags.
+///
+/// Reading such a field has the side effect of clearing it in hardware.
+/// This is why all reads in this crate take `&mut self`, see
+/// [`RegisterRead::get`][crate::register::RegisterRead::get]: a read is
+/// a state change which must not be hidden behind a shared reference.
+///
+/// # Example
+///
+/// ```
+/// use regi::{field::Field, perms::ReadToClear, register::RegisterRead};
+///
+/// const OVERRUN: Field<u32, ReadToClear, ()> = Field::new(0b1, 3);
+///
+/// // Reports an overrun only once, since the read clears the flag.
+/// fn take_overrun<R: RegisterRead<Register = u32, Marker = ()>>(status: &mut R) -> bool {
+///     status.is_set(OVERRUN)
+/// }
+/// ```
+pub struct ReadToClear;
+impl Sealed for ReadToClear {}
+impl Permission for ReadToClear {
+    const READABLE: bool = true;
+    const WRITABLE: bool = false;
+}
+impl Readable for ReadToClear {}
+
+/// Permission marker to tag "write 1 to clear" register fields, as
+/// commonly found in interrupt status registers.
+///
+/// Such fields read like regular fields, but writing a `1` to a bit
+/// clears it while writing a `0` has no effect.
+///
+/// A read-modify-write would write back every bit that was read as set
+/// and thereby clear it. This permission is therefore deliberately not
+/// [`Writable`], so no [`FieldValue`][crate::field::FieldValue] can be
+/// built for such fields and they are rejected by every read-modify-write
+/// API. Registers which consist of these fields are given this permission
+/// as a whole, and their fields are cleared individually with
+/// [`RegisterWindow::clear`][crate::mmio::RegisterWindow::clear].
+pub struct WriteOneToClear;
+impl Sealed for WriteOneToClear {}
+impl Permission for WriteOneToClear {
+    const READABLE: bool = true;
+    const WRITABLE: bool = false;
+}
+impl Readable for WriteOneToClear {}
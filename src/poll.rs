@@ -0,0 +1,118 @@
+//! Futures for awaiting changes of register state.
+//!
+//! `regi` has no knowledge of how a peripheral signals state changes,
+//! so the futures from this module rely on a user-supplied hook that
+//! registers the task's [`Waker`] to be woken, e.g. from the interrupt
+//! handler of the peripheral.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{field::Field, perms, register::RegisterRead};
+
+/// A future which completes once a field of a register is set.
+///
+/// Obtained through [`wait_for_set`].
+pub struct FieldSet<'a, W: RegisterRead, P, H> {
+    reg: &'a mut W,
+    field: Field<W::Register, P, W::Marker>,
+    register_waker: H,
+}
+
+/// Creates a future which completes once any bit of `field` is set in
+/// the register `reg`.
+///
+/// Whenever the field is found clear, `register_waker` is called with
+/// the waker of the current task before checking the field again, so
+/// that an update in between cannot be missed.
+pub fn wait_for_set<W, P, H>(
+    reg: &mut W,
+    field: Field<W::Register, P, W::Marker>,
+    register_waker: H,
+) -> FieldSet<'_, W, P, H>
+where
+    W: RegisterRead,
+    P: perms::Readable,
+    H: FnMut(&Waker),
+{
+    FieldSet {
+        reg,
+        field,
+        register_waker,
+    }
+}
+
+// The future is never structurally pinned.
+impl<'a, W: RegisterRead, P, H> Unpin for FieldSet<'a, W, P, H> {}
+
+impl<'a, W, P, H> Future for FieldSet<'a, W, P, H>
+where
+    W: RegisterRead,
+    P: perms::Readable,
+    H: FnMut(&Waker),
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.reg.is_set(this.field) {
+            return Poll::Ready(());
+        }
+
+        (this.register_waker)(cx.waker());
+        if this.reg.is_set(this.field) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        ptr,
+        task::{RawWaker, RawWakerVTable},
+    };
+
+    use super::*;
+    use crate::{mmio::RegisterWindow, perms::ReadOnly};
+
+    fn noop_waker() -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(ptr::null(), &VTABLE),
+            |_| {},
+            |_| {},
+            |_| {},
+        );
+
+        // SAFETY: The vtable functions do nothing with the data pointer.
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    #[test]
+    fn test_wait_for_set() {
+        const READY: Field<u32, ReadOnly, ()> = Field::new(0b1, 0);
+
+        let cell = Cell::new(0);
+        let mut reg = RegisterWindow::<u32, ReadOnly, ()>::from_cell(&cell);
+
+        let registered = Cell::new(0);
+        let mut future = wait_for_set(&mut reg, READY, |_| registered.set(registered.get() + 1));
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(Pin::new(&mut future).poll(&mut cx), Poll::Pending);
+        assert_eq!(Pin::new(&mut future).poll(&mut cx), Poll::Pending);
+        assert_eq!(registered.get(), 2);
+
+        cell.set(0b1);
+        assert_eq!(Pin::new(&mut future).poll(&mut cx), Poll::Ready(()));
+        assert_eq!(registered.get(), 2);
+    }
+}
@@ -0,0 +1,186 @@
+//! Access counting for profiling register usage.
+//!
+//! Wrapping a [`RegisterWindow`] with [`RegisterWindow::profiled`] counts
+//! every read and write of the register in a global registry keyed by
+//! the register's address. The counts can be queried with [`counts`] to
+//! find hot registers or redundant accesses in driver code.
+//!
+//! This module is only available with the `profile` feature, so that
+//! regular builds carry no counting overhead.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    mmio::RegisterWindow,
+    perms::{self, Permission},
+    register::{RegisterMarker, RegisterRead, RegisterWrite},
+    Int,
+};
+
+/// The maximum number of distinct registers the registry can track.
+///
+/// Accesses to registers beyond this limit are not counted.
+pub const CAPACITY: usize = 64;
+
+/// A snapshot of the number of accesses to a register.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccessCounts {
+    /// The number of reads from the register.
+    pub reads: usize,
+    /// The number of writes to the register.
+    pub writes: usize,
+}
+
+struct Slot {
+    // `0` marks a free slot; registers are never mapped at address `0`.
+    addr: AtomicUsize,
+    reads: AtomicUsize,
+    writes: AtomicUsize,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            addr: AtomicUsize::new(0),
+            reads: AtomicUsize::new(0),
+            writes: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const FREE_SLOT: Slot = Slot::new();
+static REGISTRY: [Slot; CAPACITY] = [FREE_SLOT; CAPACITY];
+
+fn find(addr: usize) -> Option<&'static Slot> {
+    REGISTRY
+        .iter()
+        .find(|slot| slot.addr.load(Ordering::Acquire) == addr)
+}
+
+fn find_or_insert(addr: usize) -> Option<&'static Slot> {
+    REGISTRY.iter().find(|slot| {
+        match slot
+            .addr
+            .compare_exchange(0, addr, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => true,
+            Err(current) => current == addr,
+        }
+    })
+}
+
+/// Gets the access counts of the register at `addr`.
+///
+/// Returns `None` if the register was never accessed through a
+/// [`Profiled`] window.
+pub fn counts(addr: usize) -> Option<AccessCounts> {
+    find(addr).map(|slot| AccessCounts {
+        reads: slot.reads.load(Ordering::Relaxed),
+        writes: slot.writes.load(Ordering::Relaxed),
+    })
+}
+
+/// A [`RegisterWindow`] that counts its accesses.
+///
+/// Obtained through [`RegisterWindow::profiled`].
+pub struct Profiled<'mmio, I: Int, P: Permission, R: RegisterMarker> {
+    window: RegisterWindow<'mmio, I, P, R>,
+    slot: Option<&'static Slot>,
+}
+
+impl<'mmio, I: Int, P: Permission, R: RegisterMarker> RegisterWindow<'mmio, I, P, R> {
+    /// Wraps the window to count its reads and writes in the global
+    /// registry of the [`profile`][crate::profile] module.
+    pub fn profiled(self) -> Profiled<'mmio, I, P, R> {
+        let slot = find_or_insert(self.addr());
+        Profiled { window: self, slot }
+    }
+}
+
+impl<'mmio, I: Int, P: Permission, R: RegisterMarker> Profiled<'mmio, I, P, R> {
+    /// Gets the current access counts of the wrapped register.
+    pub fn counts(&self) -> Option<AccessCounts> {
+        counts(self.window.addr())
+    }
+
+    /// Unwraps the [`RegisterWindow`], ending access counting.
+    pub fn into_inner(self) -> RegisterWindow<'mmio, I, P, R> {
+        self.window
+    }
+}
+
+// SAFETY: The wrapped window has `Readable` permission.
+unsafe impl<'mmio, I, P, R> RegisterRead for Profiled<'mmio, I, P, R>
+where
+    I: Int,
+    P: perms::Readable,
+    R: RegisterMarker,
+{
+    type Register = I;
+    type Marker = R;
+
+    #[inline]
+    unsafe fn get(&mut self) -> Self::Register {
+        if let Some(slot) = self.slot {
+            slot.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        self.window.get()
+    }
+}
+
+// SAFETY: The wrapped window has `Writable` permission.
+unsafe impl<'mmio, I, P, R> RegisterWrite for Profiled<'mmio, I, P, R>
+where
+    I: Int,
+    P: perms::Writable,
+    R: RegisterMarker,
+{
+    type Register = I;
+    type Marker = R;
+
+    #[inline]
+    unsafe fn set(&mut self, value: Self::Register) {
+        if let Some(slot) = self.slot {
+            slot.writes.fetch_add(1, Ordering::Relaxed);
+        }
+        self.window.set(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::{field::Field, perms::ReadWrite, register::RegisterReadWrite};
+
+    #[test]
+    fn test_profile_counts() {
+        const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+
+        let ctrl = Cell::new(0);
+        let status = Cell::new(0);
+        let ctrl_addr = ctrl.as_ptr() as usize;
+        let status_addr = status.as_ptr() as usize;
+
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&ctrl).profiled();
+        assert_eq!(reg.counts(), Some(AccessCounts::default()));
+
+        reg.write(ENABLE.make_value(1));
+        reg.is_set(ENABLE);
+        reg.modify(ENABLE.make_value(0));
+
+        assert_eq!(
+            counts(ctrl_addr),
+            Some(AccessCounts {
+                reads: 2,
+                writes: 2
+            })
+        );
+        assert_eq!(counts(status_addr), None);
+
+        let reg = reg.into_inner().profiled();
+        assert_eq!(reg.counts().unwrap().reads, 2);
+    }
+}
@@ -0,0 +1,107 @@
+//! Deferred register writes.
+//!
+//! Some drivers prepare a sequence of register writes ahead of time and
+//! apply them together, e.g. during a vertical blanking interval. A
+//! [`WriteQueue`] accumulates such writes in fixed-capacity storage and
+//! applies them to a register block in order on [`WriteQueue::flush`].
+
+use heapless::Vec;
+
+use crate::Int;
+
+/// A fixed-capacity queue of raw register writes.
+///
+/// Every write is recorded as an `(offset, value)` pair where `offset`
+/// is the byte offset of the register from the start of its block.
+pub struct WriteQueue<I, const N: usize> {
+    writes: Vec<(usize, I), N>,
+}
+
+impl<I: Int, const N: usize> WriteQueue<I, N> {
+    /// Creates an empty queue.
+    pub const fn new() -> Self {
+        Self { writes: Vec::new() }
+    }
+
+    /// Queues a write of `value` to the register at `offset`.
+    ///
+    /// Returns the write back in `Err` if the queue is full.
+    pub fn push(&mut self, offset: usize, value: I) -> Result<(), (usize, I)> {
+        self.writes.push((offset, value))
+    }
+
+    /// Gets the number of queued writes.
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Checks if there are no queued writes.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Discards all queued writes without applying them.
+    pub fn clear(&mut self) {
+        self.writes.clear();
+    }
+
+    /// Applies all queued writes to the register block at `block` in
+    /// the order they were queued, leaving the queue empty.
+    ///
+    /// # Safety
+    ///
+    /// `block` must point to a valid register block. Every queued offset
+    /// must denote a writable register of type `I` within that block,
+    /// correctly aligned for `I`. Access permissions to the individual
+    /// bit fields of the registers are not enforced.
+    pub unsafe fn flush<T>(&mut self, block: *mut T) {
+        for &(offset, value) in self.writes.iter() {
+            block
+                .cast::<u8>()
+                .add(offset)
+                .cast::<I>()
+                .write_volatile(value);
+        }
+
+        self.writes.clear();
+    }
+}
+
+impl<I: Int, const N: usize> Default for WriteQueue<I, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_write_queue_flush() {
+        let block = [Cell::new(0u32), Cell::new(0), Cell::new(0), Cell::new(0)];
+
+        let mut queue = WriteQueue::<u32, 4>::new();
+        queue.push(0x0, 0x1).unwrap();
+        queue.push(0x8, 0x2).unwrap();
+        queue.push(0x0, 0x3).unwrap();
+        queue.push(0xC, 0x4).unwrap();
+        assert_eq!(queue.push(0x4, 0x5), Err((0x4, 0x5)));
+        assert_eq!(queue.len(), 4);
+
+        // Nothing is written before the flush.
+        assert!(block.iter().all(|reg| reg.get() == 0));
+
+        // SAFETY: Offsets are aligned and within the bounds of `block`.
+        unsafe { queue.flush(block.as_ptr() as *mut [Cell<u32>; 4]) };
+        assert!(queue.is_empty());
+
+        // Writes to the same register are applied in order.
+        assert_eq!(block[0].get(), 0x3);
+        assert_eq!(block[1].get(), 0x0);
+        assert_eq!(block[2].get(), 0x2);
+        assert_eq!(block[3].get(), 0x4);
+    }
+}
@@ -4,8 +4,10 @@
 //! interacted with, while paying respect to their access
 //! permissions.
 
+use core::{fmt, marker::PhantomData, ops};
+
 use crate::{
-    field::{Field, FieldValue},
+    field::{Field, FieldValue, SplitField},
     perms, Int,
 };
 
@@ -81,6 +83,21 @@ pub unsafe trait RegisterRead {
         field.read(unsafe { self.get() })
     }
 
+    /// Reads the given [`SplitField`] out of the MMIO register and
+    /// returns its compacted value, see [`SplitField::read`].
+    ///
+    /// Unlike [`RegisterRead::get`], this function is considered safe
+    /// because the permissions to access an individual field are checked
+    /// at compile-time.
+    #[inline]
+    fn read_split<P: perms::Readable>(
+        &mut self,
+        field: SplitField<Self::Register, P, Self::Marker>,
+    ) -> Self::Register {
+        // SAFETY: The field we're reading is statically validated to be readable.
+        field.read(unsafe { self.get() })
+    }
+
     /// Reads the given field from the register and checks if any bits
     /// are set in it.
     ///
@@ -95,6 +112,87 @@ pub unsafe trait RegisterRead {
         // SAFETY: The field we're reading is statically validated to be readable.
         field.is_set(unsafe { self.get() })
     }
+
+    /// Reads the register and checks if the bits of the readable `field`
+    /// are equal to `value`, see [`FieldValue::matches`].
+    ///
+    /// As [`FieldValue`]s can also be made for write-only fields, the
+    /// `field` serves as proof of read access. Only the bits described by
+    /// `value` which also belong to `field` are inspected.
+    ///
+    /// # Example
+    ///
+    /// Polling a status register until a command has completed:
+    ///
+    /// ```
+    /// # use core::cell::Cell;
+    /// use regi::{field::Field, mmio::RegisterWindow, perms::ReadWrite, register::RegisterRead};
+    ///
+    /// const STATE: Field<u32, ReadWrite, ()> = Field::new(0b11, 4);
+    ///
+    /// # let cell = Cell::new(0x20);
+    /// # let mut status = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+    /// while !status.matches(STATE, STATE.make_value(0b10)) {
+    ///     core::hint::spin_loop();
+    /// }
+    /// ```
+    #[inline]
+    fn matches<P: perms::Readable>(
+        &mut self,
+        field: Field<Self::Register, P, Self::Marker>,
+        value: FieldValue<Self::Register, Self::Marker>,
+    ) -> bool {
+        let mask = field.select(value.mask);
+        // SAFETY: The bits we're inspecting are restricted to the
+        // statically readable `field`.
+        FieldValue::<_, Self::Marker>::from_parts(mask, value.value & mask)
+            .matches(unsafe { self.get() })
+    }
+
+    /// Reads the register once and captures its value in a
+    /// [`RegisterValue`].
+    ///
+    /// This is useful to inspect several fields from a single read or
+    /// to compute an updated value locally before committing it with
+    /// [`RegisterReadWrite::commit`].
+    #[inline]
+    fn read_once(&mut self) -> RegisterValue<Self::Register, Self::Marker> {
+        // SAFETY: This is a plain read of the whole register, as done for
+        // every field access. The captured word is not written back
+        // without going through `FieldValue`s.
+        RegisterValue::new(unsafe { self.get() })
+    }
+
+    /// Reads the register and asserts that the bits selected by
+    /// `care_mask` are equal to those in `expected`.
+    ///
+    /// This is intended for hardware bring-up tests, where `care_mask`
+    /// allows ignoring volatile or reserved bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the selected bits differ. The message lists the
+    /// expected and found values along with the mismatching bits.
+    #[track_caller]
+    fn assert_matches(&mut self, expected: Self::Register, care_mask: Self::Register)
+    where
+        Self::Register: fmt::LowerHex,
+    {
+        // SAFETY: This is a plain read of the whole register, as done for
+        // every field access, and nothing is written back.
+        let value = unsafe { self.get() };
+
+        let diff = (value ^ expected) & care_mask;
+        if diff != Self::Register::ZERO {
+            panic!(
+                "register mismatch: expected {:#x}, found {:#x} (care mask {:#x}, differing bits {:#x})",
+                expected & care_mask,
+                value & care_mask,
+                care_mask,
+                diff,
+            );
+        }
+    }
 }
 
 /// Defines write access to MMIO and CPU registers.
@@ -172,6 +270,106 @@ pub unsafe trait RegisterReadWrite {
     /// Overrides one or more fields in the register with the given
     /// [`FieldValue`], leaving everything else unchanged.
     fn modify(&mut self, field: FieldValue<Self::Register, Self::Marker>);
+
+    /// Writes a [`RegisterValue`] back to the register.
+    ///
+    /// This completes a read-modify-write sequence where the
+    /// modification was computed locally.
+    ///
+    /// A [`RegisterValue`] is only tied to its register through the
+    /// [`RegisterMarker`]. A value captured from another register with
+    /// the same marker, such as `()`, is accepted as well and writes
+    /// back the readable state of that register. Give registers distinct
+    /// markers to rule this out at compile-time.
+    ///
+    /// By default, this overrides all bits of the register through
+    /// [`RegisterReadWrite::modify`]. Types implementing [`RegisterRead`]
+    /// and [`RegisterWrite`] write the value directly instead.
+    #[inline]
+    fn commit(&mut self, value: RegisterValue<Self::Register, Self::Marker>) {
+        // Overriding the whole register is a direct write of `value`.
+        self.modify(FieldValue::from_parts(!Self::Register::ZERO, value.value));
+    }
+
+    /// Reads the register, computes a new value from it with `f` and
+    /// writes the result back.
+    ///
+    /// This performs exactly one read of the register followed by
+    /// exactly one write, with no other register access in between.
+    /// The new value can only be derived through [`FieldValue`]s, which
+    /// enforce write permissions.
+    ///
+    /// Only available for types implementing both [`RegisterRead`] and
+    /// [`RegisterWrite`].
+    #[inline]
+    fn modify_with<F>(&mut self, f: F)
+    where
+        Self: RegisterRead<
+                Register = <Self as RegisterReadWrite>::Register,
+                Marker = <Self as RegisterReadWrite>::Marker,
+            > + RegisterWrite<
+                Register = <Self as RegisterReadWrite>::Register,
+                Marker = <Self as RegisterReadWrite>::Marker,
+            >,
+        F: FnOnce(
+            RegisterValue<
+                <Self as RegisterReadWrite>::Register,
+                <Self as RegisterReadWrite>::Marker,
+            >,
+        ) -> RegisterValue<
+            <Self as RegisterReadWrite>::Register,
+            <Self as RegisterReadWrite>::Marker,
+        >,
+    {
+        let value = f(self.read_once());
+        // SAFETY: See `commit`.
+        unsafe { self.set(value.value) }
+    }
+
+    /// Flips all bits of the given `field` in the register, leaving
+    /// everything else unchanged.
+    ///
+    /// The field must be both readable and writable, as its current
+    /// state is read back before the update.
+    #[inline]
+    fn toggle<P>(
+        &mut self,
+        field: Field<<Self as RegisterReadWrite>::Register, P, <Self as RegisterReadWrite>::Marker>,
+    ) where
+        Self: RegisterRead<
+                Register = <Self as RegisterReadWrite>::Register,
+                Marker = <Self as RegisterReadWrite>::Marker,
+            > + RegisterWrite<
+                Register = <Self as RegisterReadWrite>::Register,
+                Marker = <Self as RegisterReadWrite>::Marker,
+            >,
+        P: perms::Readable + perms::Writable,
+    {
+        let mask = field.select(!<Self as RegisterReadWrite>::Register::ZERO);
+        self.modify_with(|value| value ^ FieldValue::from_parts(mask, mask));
+    }
+
+    /// Sets all bits of the given `field` in the register, leaving
+    /// everything else unchanged.
+    #[inline]
+    fn set_field<P>(&mut self, field: Field<Self::Register, P, Self::Marker>)
+    where
+        P: perms::Readable + perms::Writable,
+    {
+        let mask = field.select(!Self::Register::ZERO);
+        self.modify(FieldValue::from_parts(mask, mask));
+    }
+
+    /// Clears all bits of the given `field` in the register, leaving
+    /// everything else unchanged.
+    #[inline]
+    fn clear_field<P>(&mut self, field: Field<Self::Register, P, Self::Marker>)
+    where
+        P: perms::Readable + perms::Writable,
+    {
+        let mask = field.select(!Self::Register::ZERO);
+        self.modify(FieldValue::from_parts(mask, Self::Register::ZERO));
+    }
 }
 
 /// Provides read-modify-write semantics to eligible types by default.
@@ -187,4 +385,496 @@ where
         let value = unsafe { self.get() };
         unsafe { self.set(field.modify(value)) }
     }
+
+    fn commit(&mut self, value: RegisterValue<Self::Register, Self::Marker>) {
+        // SAFETY: The value was read from a register with the same marker,
+        // and thus the same field layout, and only altered through
+        // `FieldValue`s, which enforce write permissions. Committing the
+        // state of a different register instance is a logic error, but
+        // writes nothing the layout doesn't permit.
+        unsafe { self.set(value.value) }
+    }
+}
+
+/// A local copy of a register value.
+///
+/// Instances of this type are obtained through [`RegisterRead::read_once`]
+/// and can be used to inspect readable fields without further hardware
+/// access.
+///
+/// [`FieldValue`]s of the same register can be applied to it with the
+/// bitwise operators before writing the result back in a single access
+/// through [`RegisterReadWrite::commit`]:
+///
+/// - `value | field` replaces the bits of `field` with its value.
+/// - `value & field` clears the bits of `field` which are zero in it.
+/// - `value ^ field` toggles the bits of `field` which are one in it.
+///
+/// Bits outside the mask of `field` are always left unchanged.
+///
+/// The captured word is not hidden: its [`fmt::Debug`] output and the
+/// comparison with a raw integer include all bits of the register.
+#[derive(Clone, Copy)]
+pub struct RegisterValue<I, R> {
+    value: I,
+
+    __reg: PhantomData<R>,
+}
+
+/// Formats the captured value in hexadecimal.
+impl<I: Int + fmt::LowerHex, R: RegisterMarker> fmt::Debug for RegisterValue<I, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisterValue")
+            .field("value", &format_args!("{:#x}", self.value))
+            .finish()
+    }
+}
+
+impl<I: Int, R: RegisterMarker> RegisterValue<I, R> {
+    #[inline]
+    pub(crate) fn new(value: I) -> Self {
+        Self {
+            value,
+            __reg: PhantomData,
+        }
+    }
+
+    /// Reads the given `field` out of the captured register value.
+    #[inline]
+    pub fn read<P: perms::Readable>(&self, field: Field<I, P, R>) -> I {
+        field.read(self.value)
+    }
+
+    /// Reads the given [`SplitField`] out of the captured register value.
+    #[inline]
+    pub fn read_split<P: perms::Readable>(&self, field: SplitField<I, P, R>) -> I {
+        field.read(self.value)
+    }
+
+    /// Checks if any bits of the given `field` are set in the captured
+    /// register value.
+    #[inline]
+    pub fn is_set<P: perms::Readable>(&self, field: Field<I, P, R>) -> bool {
+        field.is_set(self.value)
+    }
+}
+
+/// Direct comparison with the full captured register value, including
+/// any reserved bits.
+impl<I: Int, R: RegisterMarker> PartialEq<I> for RegisterValue<I, R> {
+    #[inline]
+    fn eq(&self, rhs: &I) -> bool {
+        self.value == *rhs
+    }
+}
+
+/// Replaces the bits of a field in a register value.
+impl<I: Int, R: RegisterMarker> ops::BitOr<FieldValue<I, R>> for RegisterValue<I, R> {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: FieldValue<I, R>) -> Self::Output {
+        Self::new((self.value & !rhs.mask) | rhs.value)
+    }
+}
+
+/// Clears the bits of a field in a register value which are zero in
+/// the field value.
+impl<I: Int, R: RegisterMarker> ops::BitAnd<FieldValue<I, R>> for RegisterValue<I, R> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: FieldValue<I, R>) -> Self::Output {
+        Self::new(self.value & (rhs.value | !rhs.mask))
+    }
+}
+
+/// Toggles the bits of a field in a register value which are one in
+/// the field value.
+impl<I: Int, R: RegisterMarker> ops::BitXor<FieldValue<I, R>> for RegisterValue<I, R> {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: FieldValue<I, R>) -> Self::Output {
+        Self::new(self.value ^ rhs.value)
+    }
+}
+
+/// Object-safe access to a register whose type is only known at runtime.
+///
+/// This allows consumers such as emulators or test harnesses to hold
+/// registers of different widths and permissions behind one
+/// `dyn DynRegister` and dispatch accesses to them dynamically. Values
+/// are exchanged as [`u128`], which is wide enough for every [`Int`].
+///
+/// [`RawWindow`][crate::mmio::RawWindow] implements this trait for MMIO
+/// registers.
+pub trait DynRegister {
+    /// Gets the address of the register.
+    fn addr(&self) -> usize;
+
+    /// Gets the width of the register in bits.
+    fn width(&self) -> u32;
+
+    /// Reads the raw value from the register.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterRead::get`].
+    unsafe fn read_raw(&mut self) -> Result<u128, DynAccessError>;
+
+    /// Writes a raw value to the register.
+    ///
+    /// # Safety
+    ///
+    /// See [`RegisterWrite::set`].
+    unsafe fn write_raw(&mut self, value: u128) -> Result<(), DynAccessError>;
+}
+
+/// Error indicating why an access through [`DynRegister`] failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynAccessError {
+    /// The register does not permit the attempted access.
+    Denied,
+    /// The written value does not fit into the register.
+    Overflow,
+}
+
+/// Shorthand bound for any readable register stored as `I`.
+///
+/// This trims down the associated type ceremony of [`RegisterRead`]
+/// in generic code. The marker type remains accessible through
+/// [`RegisterRead::Marker`].
+///
+/// # Example
+///
+/// ```
+/// use regi::{field::Field, perms::Readable, register::ReadableRegister};
+///
+/// fn is_busy<R, P>(reg: &mut R, busy: Field<u32, P, R::Marker>) -> bool
+/// where
+///     R: ReadableRegister<u32>,
+///     P: Readable,
+/// {
+///     reg.is_set(busy)
+/// }
+/// ```
+pub trait ReadableRegister<I: Int>: RegisterRead<Register = I> {}
+
+impl<T: RegisterRead<Register = I>, I: Int> ReadableRegister<I> for T {}
+
+/// Shorthand bound for any writable register stored as `I`.
+///
+/// This trims down the associated type ceremony of [`RegisterWrite`]
+/// in generic code. The marker type remains accessible through
+/// [`RegisterWrite::Marker`].
+///
+/// # Example
+///
+/// ```
+/// use regi::{field::Field, perms::Writable, register::WritableRegister};
+///
+/// fn enable<W, P>(reg: &mut W, enable: Field<u32, P, W::Marker>)
+/// where
+///     W: WritableRegister<u32>,
+///     P: Writable,
+/// {
+///     reg.write(enable.make_value(1));
+/// }
+/// ```
+pub trait WritableRegister<I: Int>: RegisterWrite<Register = I> {}
+
+impl<T: RegisterWrite<Register = I>, I: Int> WritableRegister<I> for T {}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+    use crate::{
+        mmio::RegisterWindow,
+        perms::{ReadWrite, Writable, WriteOnly},
+    };
+
+    #[test]
+    fn test_writable_register_bound() {
+        fn write_all<W, P>(reg: &mut W, field: Field<u32, P, W::Marker>)
+        where
+            W: WritableRegister<u32>,
+            P: Writable,
+        {
+            reg.write(field.make_value(u32::MAX));
+        }
+
+        let cell = Cell::new(0);
+        write_all(
+            &mut RegisterWindow::<u32, WriteOnly, ()>::from_cell(&cell),
+            Field::<u32, WriteOnly, ()>::new(0xF, 0),
+        );
+        assert_eq!(cell.get(), 0xF);
+
+        write_all(
+            &mut RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell),
+            Field::<u32, ReadWrite, ()>::new(0xFF, 0),
+        );
+        assert_eq!(cell.get(), 0xFF);
+    }
+
+    #[test]
+    fn test_register_value_ops() {
+        const LOW: Field<u8, ReadWrite, ()> = Field::new(0xF, 0);
+
+        let cell = Cell::new(0b1010_0110);
+        let mut reg = RegisterWindow::<u8, ReadWrite, ()>::from_cell(&cell);
+
+        let current = reg.read_once();
+        assert_eq!(current.read(LOW), 0b0110);
+        assert!(current.is_set(LOW));
+
+        assert_eq!((current | LOW.make_value(0b1001)).read(LOW), 0b1001);
+        assert_eq!((current & LOW.make_value(0b0011)).read(LOW), 0b0010);
+        assert_eq!((current ^ LOW.make_value(0b0011)).read(LOW), 0b0101);
+
+        let new = current | LOW.make_value(0b0001);
+        assert_eq!(cell.get(), 0b1010_0110);
+        reg.commit(new);
+        assert_eq!(cell.get(), 0b1010_0001);
+    }
+
+    #[test]
+    fn test_read_split() {
+        const SPLIT: SplitField<u8, ReadWrite, ()> = SplitField::new(0b1110_0011);
+
+        let cell = Cell::new(0b1010_0001);
+        let mut reg = RegisterWindow::<u8, ReadWrite, ()>::from_cell(&cell);
+        assert_eq!(reg.read_split(SPLIT), 0b10101);
+        assert_eq!(reg.read_once().read_split(SPLIT), 0b10101);
+
+        reg.modify(SPLIT.make_value(0b01010));
+        assert_eq!(cell.get(), 0b0100_0010);
+        assert_eq!(reg.read_split(SPLIT), 0b01010);
+    }
+
+    #[test]
+    fn test_assert_matches() {
+        let cell = Cell::new(0x8000_1234);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        reg.assert_matches(0x8000_1234, u32::MAX);
+        reg.assert_matches(0x0000_1234, 0x0000_FFFF);
+        reg.assert_matches(0xFFFF_FFFF, 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "register mismatch: expected 0x1200, found 0x1234 (care mask 0xffff, differing bits 0x34)"
+    )]
+    fn test_assert_matches_mismatch() {
+        let cell = Cell::new(0x8000_1234);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        reg.assert_matches(0x0000_1200, 0x0000_FFFF);
+    }
+
+    #[test]
+    fn test_register_value_eq() {
+        let cell = Cell::new(0x8000_1234);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        assert_eq!(reg.read_once(), 0x8000_1234);
+        assert_ne!(reg.read_once(), 0x0000_1234);
+    }
+
+    #[test]
+    fn test_register_value_debug() {
+        extern crate std;
+
+        let cell = Cell::new(0xDEAD_BEEF);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        assert_eq!(
+            std::format!("{:?}", reg.read_once()),
+            "RegisterValue { value: 0xdeadbeef }"
+        );
+    }
+
+    // A register at `index` in a backing array standing in for MMIO.
+    struct Slot<'a> {
+        regs: &'a mut [u16; 4],
+        index: usize,
+    }
+
+    unsafe impl RegisterRead for Slot<'_> {
+        type Register = u16;
+        type Marker = ();
+
+        unsafe fn get(&mut self) -> u16 {
+            self.regs[self.index]
+        }
+    }
+
+    unsafe impl RegisterWrite for Slot<'_> {
+        type Register = u16;
+        type Marker = ();
+
+        unsafe fn set(&mut self, value: u16) {
+            self.regs[self.index] = value;
+        }
+    }
+
+    #[test]
+    fn test_read_write_modify() {
+        const MODE: Field<u16, ReadWrite, ()> = Field::new(0b11, 4);
+        const ENABLE: Field<u16, ReadWrite, ()> = Field::new(0b1, 15);
+
+        let mut regs = [0x0000, 0x0F0F, 0x0000, 0x0000];
+        let mut reg = Slot {
+            regs: &mut regs,
+            index: 1,
+        };
+
+        reg.modify(MODE.make_value(0b10) | ENABLE.make_value(1));
+        assert_eq!(reg.read(MODE), 0b10);
+        assert_eq!(regs, [0x0000, 0x8F2F, 0x0000, 0x0000]);
+    }
+
+    #[test]
+    fn test_read_write_default_commit() {
+        // Implementors which only provide `modify` get `commit` for free.
+        struct ModifyOnly(u16);
+
+        unsafe impl RegisterReadWrite for ModifyOnly {
+            type Register = u16;
+            type Marker = ();
+
+            fn modify(&mut self, field: FieldValue<u16, ()>) {
+                self.0 = field.modify(self.0);
+            }
+        }
+
+        const LOW: Field<u16, ReadWrite, ()> = Field::new(0xFF, 0);
+
+        let mut regs = [0x0000, 0x12AB, 0x0000, 0x0000];
+        let value = Slot {
+            regs: &mut regs,
+            index: 1,
+        }
+        .read_once();
+
+        let mut reg = ModifyOnly(0xFFFF);
+        reg.commit(value | LOW.make_value(0xCD));
+        assert_eq!(reg.0, 0x12CD);
+    }
+
+    #[test]
+    fn test_read_write_modify_with() {
+        const READY: Field<u8, ReadWrite, ()> = Field::new(0b1, 0);
+        const TOGGLE: Field<u8, ReadWrite, ()> = Field::new(0b1, 7);
+
+        let cell = Cell::new(0b0000_0001);
+        let mut reg = RegisterWindow::<u8, ReadWrite, ()>::from_cell(&cell);
+
+        let flip = |value: RegisterValue<u8, ()>| {
+            if value.is_set(READY) {
+                value ^ TOGGLE.make_value(1)
+            } else {
+                value
+            }
+        };
+
+        reg.modify_with(flip);
+        assert_eq!(cell.get(), 0b1000_0001);
+        reg.modify_with(flip);
+        assert_eq!(cell.get(), 0b0000_0001);
+
+        cell.set(0b0000_0000);
+        reg.modify_with(flip);
+        assert_eq!(cell.get(), 0b0000_0000);
+    }
+
+    #[test]
+    fn test_register_matches() {
+        const STATE: Field<u32, ReadWrite, ()> = Field::new(0b11, 4);
+        const ERROR: Field<u32, ReadWrite, ()> = Field::new(0b1, 31);
+        const CMD: Field<u32, WriteOnly, ()> = Field::new(0xF, 8);
+
+        let cell = Cell::new(0x0000_0025);
+        let mut reg = RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell);
+
+        assert!(reg.matches(STATE, STATE.make_value(0b10)));
+        assert!(!reg.matches(STATE, STATE.make_value(0b11)));
+        assert!(reg.matches(ERROR, ERROR.make_value(0)));
+
+        cell.set(0x8000_0025);
+        assert!(!reg.matches(ERROR, ERROR.make_value(0)));
+        assert!(reg.matches(STATE, STATE.make_value(0b10)));
+
+        // Bits of `value` outside of the readable field are never inspected.
+        assert!(reg.matches(STATE, STATE.make_value(0b10) | CMD.make_value(0xF)));
+    }
+
+    #[test]
+    fn test_read_to_clear() {
+        use crate::perms::ReadToClear;
+
+        // A status register whose `OVERRUN` flag clears on every read.
+        struct Status {
+            value: u32,
+        }
+
+        unsafe impl RegisterRead for Status {
+            type Register = u32;
+            type Marker = ();
+
+            unsafe fn get(&mut self) -> u32 {
+                let value = self.value;
+                self.value &= !0b1000;
+                value
+            }
+        }
+
+        const OVERRUN: Field<u32, ReadToClear, ()> = Field::new(0b1, 3);
+
+        let mut status = Status { value: 0b1001 };
+        assert!(status.is_set(OVERRUN));
+        assert!(!status.is_set(OVERRUN));
+        assert_eq!(status.value, 0b0001);
+    }
+
+    #[test]
+    fn test_read_write_toggle() {
+        const LED: Field<u8, ReadWrite, ()> = Field::new(0b1, 2);
+        const MODE: Field<u8, ReadWrite, ()> = Field::new(0b11, 4);
+
+        let cell = Cell::new(0b1001_0001);
+        let mut reg = RegisterWindow::<u8, ReadWrite, ()>::from_cell(&cell);
+
+        reg.toggle(LED);
+        assert_eq!(cell.get(), 0b1001_0101);
+        reg.toggle(LED);
+        assert_eq!(cell.get(), 0b1001_0001);
+
+        reg.toggle(MODE);
+        assert_eq!(cell.get(), 0b1010_0001);
+        reg.toggle(MODE);
+        assert_eq!(cell.get(), 0b1001_0001);
+    }
+
+    #[test]
+    fn test_read_write_set_clear_field() {
+        const MODE: Field<u16, ReadWrite, ()> = Field::new(0b111, 4);
+
+        let mut regs = [0x0000, 0xA50F, 0xFFFF, 0x0000];
+        let mut reg = Slot {
+            regs: &mut regs,
+            index: 1,
+        };
+
+        reg.set_field(MODE);
+        assert_eq!(reg.read(MODE), 0b111);
+        reg.clear_field(MODE);
+        assert_eq!(reg.read(MODE), 0);
+
+        assert_eq!(regs, [0x0000, 0xA50F & !0x70, 0xFFFF, 0x0000]);
+    }
 }
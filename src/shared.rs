@@ -0,0 +1,94 @@
+//! Shared access to registers from multiple execution contexts.
+//!
+//! Peripherals are commonly accessed from both the main program and
+//! interrupt handlers. [`Shared`] implements the established pattern of
+//! guarding them with a [`critical_section::Mutex`], so that registers
+//! can be stored in a `static` and accessed safely from anywhere.
+//!
+//! Like the mutex, [`Shared`] is only [`Sync`] when the wrapped value is
+//! [`Send`]. [`RegisterWindow`][crate::mmio::RegisterWindow]s are not, so
+//! registers shared this way must be accessed through
+//! [`AtomicRegisterWindow`][crate::mmio::AtomicRegisterWindow]s.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+/// A value, typically a register window, guarded by a critical section.
+pub struct Shared<T> {
+    inner: Mutex<RefCell<T>>,
+}
+
+impl<T> Shared<T> {
+    /// Wraps `value` for shared access.
+    pub const fn new(value: T) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(value)),
+        }
+    }
+
+    /// Enters a critical section and calls `f` with exclusive access to
+    /// the wrapped value, returning its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called reentrantly from within `f`.
+    pub fn with<U>(&self, f: impl FnOnce(&mut T) -> U) -> U {
+        critical_section::with(|cs| f(&mut self.inner.borrow_ref_mut(cs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use static_assertions::{assert_impl_all, assert_not_impl_all};
+
+    use super::*;
+    use crate::{
+        field::Field,
+        mmio::{AtomicRegisterWindow, RegisterWindow},
+        perms::ReadWrite,
+        register::{RegisterRead, RegisterWrite},
+    };
+
+    static STATUS: AtomicU32 = AtomicU32::new(0);
+    static SHARED_STATUS: Shared<Option<AtomicRegisterWindow<'static, u32, ReadWrite, ()>>> =
+        Shared::new(None);
+
+    #[test]
+    fn test_shared_with() {
+        const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+
+        let cell = Cell::new(0);
+        let shared = Shared::new(RegisterWindow::<u32, ReadWrite, ()>::from_cell(&cell));
+
+        shared.with(|reg| reg.write(ENABLE.make_value(1)));
+        assert!(shared.with(|reg| reg.is_set(ENABLE)));
+        assert_eq!(cell.get(), 1);
+    }
+
+    #[test]
+    fn test_shared_static() {
+        const ENABLE: Field<u32, ReadWrite, ()> = Field::new(0b1, 0);
+
+        assert_impl_all!(Shared<AtomicRegisterWindow<'static, u32, ReadWrite, ()>>: Sync);
+        assert_not_impl_all!(Shared<RegisterWindow<'static, u32, ReadWrite, ()>>: Sync);
+
+        SHARED_STATUS.with(|reg| {
+            *reg = Some(AtomicRegisterWindow::from_atomic(&STATUS, Ordering::AcqRel));
+        });
+        SHARED_STATUS.with(|reg| reg.as_mut().unwrap().write(ENABLE.make_value(1)));
+        assert_eq!(STATUS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shared_reentrant() {
+        let shared = Shared::new(0u32);
+        shared.with(|_| shared.with(|_| ()));
+    }
+}